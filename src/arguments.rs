@@ -1,3 +1,5 @@
+use crate::InputFormat;
+use chrono::Duration;
 use clap::Parser;
 
 #[derive(Parser)]
@@ -19,21 +21,62 @@ pub struct Args {
     )]
     pub display_info: Vec<String>,
 
+    #[clap(
+        long,
+        value_parser = parse_offset,
+        help = "Stop the replay at this offset from the first frame (HH:MM:SS or seconds)"
+    )]
+    pub end_at: Option<Duration>,
+
     #[clap(short = 'D', long, default_value = None)]
     pub downlink_log: Option<String>,
 
     #[clap(short = 'l', long, default_value = None)]
     pub error_log: Option<String>,
 
+    #[clap(
+        short = 'x',
+        long,
+        action = clap::ArgAction::Count,
+        help = "Repair CRC errors in DF11/17/18\n -x - single bit, -xx - up to two bits (DF17/18)"
+    )]
+    pub fix: u8,
+
     #[clap(short, long, default_value = None, help = "Process only specific DF messages\n -f 21 -f 4 - DF4 and DF21,\n -f 21 - only DF21, etc")]
     pub filter: Option<Vec<u32>>,
 
-    #[clap(short='F', long, default_value = None)]
-    pub format: Option<String>,
+    #[clap(
+        short = 'F',
+        long,
+        value_enum,
+        default_value_t = InputFormat::Auto,
+        help = "Input format, auto detected from the first bytes by default"
+    )]
+    pub format: InputFormat,
+
+    #[clap(
+        long,
+        help = "Serve dump1090 compatible /data/aircraft.json over HTTP on this port, e.g. 8080"
+    )]
+    pub http_port: Option<u16>,
+
+    #[clap(
+        long,
+        default_value = "60",
+        help = "Accept DF0/4/5/16/20/21 only from addresses seen in DF11/17/18\nwithin the last seconds, 0 accepts all"
+    )]
+    pub icao_ttl: i64,
 
     #[clap(short='M', long, default_value = None)]
     pub log_messages: Option<Vec<u32>>,
 
+    #[clap(
+        long,
+        default_value = "1",
+        help = "Leave out positions with a lower NIC (NUCp for ADS-B version 0)\nfrom the SBS and aircraft.json feeds, 0 keeps all"
+    )]
+    pub min_nic: u32,
+
     #[clap(
         short,
         long,
@@ -42,6 +85,13 @@ pub struct Args {
     )]
     pub order_by: Vec<String>,
 
+    #[clap(
+        long,
+        default_value = "2",
+        help = "Reject positions farther from the last one than the ground speed\ntimes this factor allows, 0 accepts all"
+    )]
+    pub position_margin: f64,
+
     #[clap(
         short = 'O',
         long,
@@ -49,9 +99,19 @@ pub struct Args {
     )]
     pub observer_coord: Option<String>,
 
-    #[clap(short = 'R', long, help = "Relaxed Capabilities check EHS")]
+    #[clap(
+        short = 'R',
+        long,
+        help = "Accept EHS registers the transponder does not advertise"
+    )]
     pub relaxed: bool,
 
+    #[clap(
+        long,
+        help = "Serve BaseStation (SBS-1) MSG lines on this TCP port, e.g. 30003"
+    )]
+    pub sbs_port: Option<u16>,
+
     #[clap(
         short,
         long,
@@ -60,6 +120,21 @@ pub struct Args {
     )]
     pub source: String,
 
+    #[clap(
+        long,
+        conflicts_with = "tcp",
+        value_parser = parse_speed,
+        help = "Replay the recording in real time, sped up by the factor (e.g. 1x, 10x, 60x)\nType Enter to pause/resume, s to step, +/- to change speed, q to quit"
+    )]
+    pub speed: Option<f64>,
+
+    #[clap(
+        long,
+        value_parser = parse_offset,
+        help = "Fast forward to this offset from the first frame (HH:MM:SS or seconds)"
+    )]
+    pub start_at: Option<Duration>,
+
     #[clap(
         short,
         long,
@@ -78,3 +153,53 @@ pub struct Args {
     #[clap(short = 'U', long, help = "Use Plain::update() exclusively")]
     pub use_update_method: bool,
 }
+
+impl Args {
+    /// Returns `true` when the recording is replayed in time or within a window.
+    pub fn is_replay(&self) -> bool {
+        self.tcp.is_empty()
+            && (self.speed.is_some() || self.start_at.is_some() || self.end_at.is_some())
+    }
+}
+
+fn parse_speed(s: &str) -> Result<f64, String> {
+    let speed = s
+        .trim_end_matches(['x', 'X'])
+        .parse::<f64>()
+        .map_err(|e| e.to_string())?;
+    if speed > 0.0 && speed.is_finite() {
+        Ok(speed)
+    } else {
+        Err("speed must be positive".to_string())
+    }
+}
+
+fn parse_offset(s: &str) -> Result<Duration, String> {
+    let seconds = s.split(':').try_fold(0i64, |acc, part| {
+        part.parse::<u32>()
+            .map(|v| acc * 60 + v as i64)
+            .map_err(|e| format!("{}: {}", part, e))
+    })?;
+    Ok(Duration::seconds(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_speed() {
+        assert_eq!(parse_speed("10x"), Ok(10.0));
+        assert_eq!(parse_speed("0.5"), Ok(0.5));
+        assert!(parse_speed("0x").is_err());
+        assert!(parse_speed("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("01:02:03"), Ok(Duration::seconds(3723)));
+        assert_eq!(parse_offset("05:00"), Ok(Duration::seconds(300)));
+        assert_eq!(parse_offset("90"), Ok(Duration::seconds(90)));
+        assert!(parse_offset("1:xx").is_err());
+    }
+}
@@ -2,8 +2,13 @@ use std::collections::BTreeMap;
 
 use chrono::{DateTime, Utc};
 
+use crate::InputFormat;
+
 pub(crate) struct AppCounters {
     pub(crate) df_count: BTreeMap<u32, i32>,
+    pub(crate) invalid_count: BTreeMap<InputFormat, u32>,
+    pub(crate) rejected_count: u32,
+    pub(crate) repaired_count: BTreeMap<u32, u32>,
     pub(crate) timestamp: DateTime<Utc>,
     pub(crate) cleanup_count: u32,
 }
@@ -12,6 +17,9 @@ impl AppCounters {
     pub(crate) fn from_update_interval(update: i64) -> Self {
         AppCounters {
             df_count: BTreeMap::new(),
+            invalid_count: BTreeMap::new(),
+            rejected_count: 0,
+            repaired_count: BTreeMap::new(),
             timestamp: chrono::Utc::now() + chrono::Duration::seconds(update),
             cleanup_count: 0u32,
         }
@@ -21,6 +29,18 @@ impl AppCounters {
         *self.df_count.entry(df).or_insert(1) += 1;
     }
 
+    pub(crate) fn update_invalid_count(&mut self, format: InputFormat) {
+        *self.invalid_count.entry(format).or_insert(0) += 1;
+    }
+
+    pub(crate) fn increment_rejected_count(&mut self) {
+        self.rejected_count += 1;
+    }
+
+    pub(crate) fn update_repaired_count(&mut self, bits: u32) {
+        *self.repaired_count.entry(bits).or_insert(0) += 1;
+    }
+
     pub(crate) fn reset_cleanup_count(&mut self) {
         self.cleanup_count = 0;
     }
@@ -44,6 +64,38 @@ impl AppCounters {
         );
     }
 
+    pub(crate) fn print_invalid_count_line(&self) {
+        if !self.invalid_count.is_empty() {
+            println!(
+                "Invalid {}",
+                self.invalid_count
+                    .iter()
+                    .fold(String::new(), |acc, (format, count)| {
+                        acc + &format!("{}:{} ", format, count)
+                    })
+            );
+        }
+    }
+
+    pub(crate) fn print_rejected_count_line(&self) {
+        if self.rejected_count > 0 {
+            println!("Rejected by parity or address:{}", self.rejected_count);
+        }
+    }
+
+    pub(crate) fn print_repaired_count_line(&self) {
+        if !self.repaired_count.is_empty() {
+            println!(
+                "Repaired {}",
+                self.repaired_count
+                    .iter()
+                    .fold(String::new(), |acc, (bits, count)| {
+                        acc + &format!("{}-bit:{} ", bits, count)
+                    })
+            );
+        }
+    }
+
     pub(crate) fn is_time_to_refresh(&self, now: &DateTime<Utc>, update: i64) -> bool {
         now.signed_duration_since(self.timestamp).num_seconds() > update
     }
@@ -3,6 +3,9 @@ mod bds;
 mod country;
 mod downlink;
 mod ehs;
+mod encounters;
+mod frame;
+mod icao_filter;
 mod meteo;
 mod observer;
 mod plane;
@@ -10,7 +13,11 @@ mod planes;
 mod utils;
 
 pub use adsb::*;
+pub use bds::{BdsStatistics, bds_statistics};
 pub use downlink::*;
+pub use encounters::Encounter;
+pub use frame::Frame;
+pub use icao_filter::set_icao_filter;
 pub use observer::*;
 pub use plane::*;
 pub use planes::*;
@@ -19,4 +26,5 @@ pub use utils::*;
 use bds::*;
 use country::*;
 use ehs::*;
+pub(crate) use icao_filter::{is_known_icao, remember_icao};
 use meteo::*;
@@ -1,23 +1,45 @@
 mod acas;
+mod air_air;
 mod ais;
 mod altitude;
+mod control_field;
+mod emergency;
+mod flight_status;
 mod ground_movement;
 mod icao;
+mod integrity;
+mod operational_status;
 mod position;
 mod squawk;
 mod surveillance_status;
+mod target_state;
 mod version;
 mod vertical_rate;
 
+pub use acas::{ResolutionAdvisory, Threat};
+pub use air_air::AirAirSurveillance;
+pub use control_field::{AddressType, address_type};
+pub use emergency::Emergency;
+pub use flight_status::{DownlinkRequest, FlightStatus};
 pub use icao::get_icao;
+pub use integrity::PositionIntegrity;
+pub use operational_status::OperationalStatus;
+pub use target_state::{AutopilotModes, TargetState};
 
-pub(crate) use acas::threat_encounter;
+pub(crate) use acas::{resolution_advisory, threat_encounter};
+pub(crate) use air_air::{air_air_surveillance, mv_resolution_advisory};
 pub(crate) use ais::ais;
 pub(crate) use altitude::{altitude, altitude_delta, altitude_gnss};
+pub(crate) use control_field::is_extended_squitter;
+pub(crate) use emergency::emergency_state;
+pub(crate) use flight_status::{downlink_request, flight_status};
 pub(crate) use ground_movement::ground_movement;
 pub(crate) use icao::get_wake_turbulence_category;
-pub(crate) use position::{cpr, cpr_location};
-pub(crate) use squawk::squawk;
+pub(crate) use integrity::{NicSupplements, nic_supplement_b, position_integrity};
+pub(crate) use operational_status::operational_status;
+pub(crate) use position::{cpr, cpr_local_location, cpr_location, cpr_surface_location};
+pub(crate) use squawk::{emergency_squawk, squawk};
 pub(crate) use surveillance_status::surveillance_status;
+pub(crate) use target_state::target_state;
 pub(crate) use version::version;
 pub(crate) use vertical_rate::vertical_rate;
@@ -1,3 +1,6 @@
+use crate::range_value;
+use std::fmt::{self, Display};
+
 pub(crate) fn threat_encounter(message: &[u32]) -> Option<char> {
     let multiple_threats = message[14] & 1 == 1;
     let single_threat = (message[10] >> 3) & 1 == 1;
@@ -10,6 +13,203 @@ pub(crate) fn threat_encounter(message: &[u32]) -> Option<char> {
     }
 }
 
+/// Identity of the threat of a resolution advisory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threat {
+    /// Mode S address of the threat (TTI 1)
+    Icao(u32),
+    /// Altitude (ft), range (NM) and bearing (degrees) of a threat without a Mode S address (TTI 2)
+    Position {
+        altitude: Option<i32>,
+        range: Option<f64>,
+        bearing: Option<u32>,
+    },
+}
+
+/// ACAS resolution advisory, broadcast in TC 28 subtype 2 and reported in BDS 3.0.
+///
+/// Both carry the same fields in the 56 bits following bit 32.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolutionAdvisory {
+    /// Active resolution advisories (14 bits)
+    pub ara: u32,
+    /// Resolution advisory complements (4 bits)
+    pub rac: u32,
+    /// RA terminated
+    pub terminated: bool,
+    /// Multiple threat encounter
+    pub multiple_threats: bool,
+    pub threat: Option<Threat>,
+}
+
+impl ResolutionAdvisory {
+    fn ara_bit(&self, bit: u32) -> bool {
+        // ARA bits are numbered 41-54
+        (self.ara >> (54 - bit)) & 1 == 1
+    }
+
+    /// Vertical sense of the advisory, `true` for upward.
+    pub fn upward(&self) -> Option<bool> {
+        if self.ara_bit(41) {
+            Some(!self.ara_bit(43))
+        } else if self.ara_bit(42) {
+            Some(true)
+        } else if self.ara_bit(44) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` for a corrective advisory, requiring a change of the vertical speed.
+    pub fn corrective(&self) -> bool {
+        if self.ara_bit(41) {
+            self.ara_bit(42)
+        } else {
+            self.ara_bit(42) || self.ara_bit(44)
+        }
+    }
+
+    /// Returns `true` when an increased climb or descent rate is required, reported for one threat only.
+    pub fn increase(&self) -> bool {
+        self.ara_bit(41) && self.ara_bit(44)
+    }
+
+    /// Returns `true` when a positive climb is required, reported for multiple threats only.
+    pub fn positive_climb(&self) -> bool {
+        !self.ara_bit(41) && self.ara_bit(43)
+    }
+
+    /// Returns `true` when a positive descent is required, reported for multiple threats only.
+    pub fn positive_descent(&self) -> bool {
+        !self.ara_bit(41) && self.ara_bit(45)
+    }
+
+    /// Returns `true` after a reversal of the vertical sense.
+    pub fn reversal(&self) -> bool {
+        self.ara_bit(if self.ara_bit(41) { 45 } else { 47 })
+    }
+
+    /// Returns `true` when the advisory crosses the altitude of the threat.
+    pub fn crossing(&self) -> bool {
+        self.ara_bit(46)
+    }
+
+    /// Returns `true` while the advisory is in force.
+    pub fn is_active(&self) -> bool {
+        !self.terminated && (self.ara != 0 || self.multiple_threats)
+    }
+
+    /// Four character abbreviation shown in the table.
+    pub fn abbreviation(&self) -> &'static str {
+        match (self.is_active(), self.upward()) {
+            (false, _) => "COC",
+            (true, Some(true)) => "RA\u{2191}",
+            (true, Some(false)) => "RA\u{2193}",
+            (true, None) => "RA",
+        }
+    }
+
+    fn advisory(&self) -> &'static str {
+        if !self.is_active() {
+            return "clear of conflict";
+        }
+        match (self.ara_bit(41), self.upward(), self.ara_bit(47)) {
+            (true, Some(true), true) => "climb",
+            (true, Some(false), true) => "descend",
+            (true, _, false) if self.ara_bit(42) => "adjust vertical speed",
+            (true, _, false) => "monitor vertical speed",
+            (false, Some(true), _) => "multiple threats: climb",
+            (false, Some(false), _) => "multiple threats: descend",
+            _ => "multiple threats",
+        }
+    }
+}
+
+impl Display for ResolutionAdvisory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.advisory())?;
+        if self.is_active() {
+            for (flag, modifier) in [
+                (self.increase(), "increase"),
+                (self.positive_climb(), "positive climb"),
+                (self.positive_descent(), "positive descent"),
+                (self.reversal(), "reversal"),
+                (self.crossing(), "crossing"),
+            ] {
+                if flag {
+                    write!(f, "; {}", modifier)?;
+                }
+            }
+        }
+        for (bit, complement) in [
+            (3, "do not pass below"),
+            (2, "do not pass above"),
+            (1, "do not turn left"),
+            (0, "do not turn right"),
+        ] {
+            if (self.rac >> bit) & 1 == 1 {
+                write!(f, "; {}", complement)?;
+            }
+        }
+        match self.threat {
+            Some(Threat::Icao(icao)) => write!(f, "; threat {:06X}", icao),
+            Some(Threat::Position {
+                altitude,
+                range,
+                bearing,
+            }) => {
+                write!(f, "; threat")?;
+                if let Some(altitude) = altitude {
+                    write!(f, " {}ft", altitude)?;
+                }
+                if let Some(range) = range {
+                    write!(f, " {:.1}NM", range)?;
+                }
+                if let Some(bearing) = bearing {
+                    write!(f, " {}\u{b0}", bearing)?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Decodes the resolution advisory of TC 28 subtype 2 or BDS 3.0.
+pub(crate) fn resolution_advisory(message: &[u32]) -> Option<ResolutionAdvisory> {
+    let threat = match range_value(message, 61, 62)? {
+        1 => Some(Threat::Icao(range_value(message, 63, 86)?)),
+        2 => Some(Threat::Position {
+            altitude: range_value(message, 63, 75).and_then(ac13_altitude),
+            range: range_value(message, 76, 82)
+                .filter(|range| (1..=126).contains(range))
+                .map(|range| (range - 1) as f64 / 10.0),
+            bearing: range_value(message, 83, 88)
+                .filter(|bearing| (1..=60).contains(bearing))
+                .map(|bearing| (bearing - 1) * 6),
+        }),
+        _ => None,
+    };
+    Some(ResolutionAdvisory {
+        ara: range_value(message, 41, 54)?,
+        rac: range_value(message, 55, 58)?,
+        terminated: range_value(message, 59, 59)? == 1,
+        multiple_threats: range_value(message, 60, 60)? == 1,
+        threat,
+    })
+}
+
+/// Decodes a 13-bit altitude code in 25 ft increments, `None` for metric or Gillham altitudes.
+fn ac13_altitude(code: u32) -> Option<i32> {
+    let m_bit = (code >> 6) & 1;
+    let q_bit = (code >> 4) & 1;
+    (m_bit == 0 && q_bit == 1).then(|| {
+        let n = ((code >> 7) << 5) | (((code >> 5) & 1) << 4) | (code & 0xF);
+        n as i32 * 25 - 1000
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -21,4 +221,69 @@ mod tests {
             assert_eq!(threat_encounter(&message), Some('\u{2072}'));
         }
     }
+
+    #[test]
+    fn test_resolution_advisory() {
+        let message =
+            decoder::get_message("8D40621DE2C20005210358D28CB5").expect("Failed to get message");
+        let ra = resolution_advisory(&message).expect("Failed to decode RA");
+        assert_eq!(ra.threat, Some(Threat::Icao(0x4840D6)));
+        assert_eq!(ra.upward(), Some(true));
+        assert_eq!(ra.abbreviation(), "RA\u{2191}");
+        assert_eq!(ra.to_string(), "climb; threat 4840D6");
+
+        let message =
+            decoder::get_message("8D40621DE2E20008D70550A28CCE").expect("Failed to get message");
+        let ra = resolution_advisory(&message).expect("Failed to decode RA");
+        assert_eq!(ra.upward(), Some(false));
+        assert_eq!(ra.to_string(), "descend; threat 10000ft 2.0NM 90\u{b0}");
+
+        let message =
+            decoder::get_message("8D40621DE2000020000000BC2920").expect("Failed to get message");
+        let ra = resolution_advisory(&message).expect("Failed to decode RA");
+        assert!(!ra.is_active());
+        assert_eq!(ra.abbreviation(), "COC");
+
+        // Multiple threats, upward correction and positive climb, crossing a threat altitude
+        let ra = ResolutionAdvisory {
+            ara: 0b01100100000000,
+            rac: 0,
+            terminated: false,
+            multiple_threats: true,
+            threat: None,
+        };
+        assert!(ra.corrective());
+        assert!(ra.positive_climb());
+        assert!(!ra.increase());
+        assert!(!ra.reversal());
+        assert!(ra.crossing());
+        assert_eq!(
+            ra.to_string(),
+            "multiple threats: climb; positive climb; crossing"
+        );
+
+        // Multiple threats, downward correction and positive descent, with a sense reversal
+        let ra = ResolutionAdvisory {
+            ara: 0b00011010000000,
+            ..ra
+        };
+        assert_eq!(ra.upward(), Some(false));
+        assert!(ra.positive_descent());
+        assert!(ra.reversal());
+        assert!(!ra.crossing());
+        assert_eq!(
+            ra.to_string(),
+            "multiple threats: descend; positive descent; reversal"
+        );
+
+        // One threat, corrective climb crossing the threat altitude
+        let ra = ResolutionAdvisory {
+            ara: 0b11000110000000,
+            multiple_threats: false,
+            ..ra
+        };
+        assert!(ra.crossing());
+        assert!(!ra.reversal());
+        assert_eq!(ra.to_string(), "climb; crossing");
+    }
 }
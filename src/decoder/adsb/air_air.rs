@@ -0,0 +1,123 @@
+use super::{ResolutionAdvisory, resolution_advisory};
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Air-air surveillance fields of a short (DF0) or long (DF16) ACAS reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AirAirSurveillance {
+    /// Vertical status (VS)
+    pub on_ground: bool,
+    /// Cross-link capability (CC), DF0 only
+    pub cross_link: Option<bool>,
+    /// Sensitivity level (SL), 0 ACAS inoperative, 1 standby, 2 TA only, 3-7 TA/RA
+    pub sensitivity_level: u32,
+    /// Reply information (RI), the ACAS capability or, 8-14, the maximum airspeed class
+    pub reply_information: u32,
+}
+
+impl AirAirSurveillance {
+    /// Returns the ACAS capability of RI 0-7, `None` for the airspeed classes.
+    pub fn acas_capability(&self) -> Option<&'static str> {
+        match self.reply_information {
+            0 => Some("no ACAS"),
+            2 => Some("ACAS RA inhibited"),
+            3 => Some("ACAS vertical RA"),
+            4 => Some("ACAS vertical and horizontal RA"),
+            _ => None,
+        }
+    }
+
+    /// Returns the maximum true airspeed (kt) class of RI 9-14.
+    pub fn max_airspeed(&self) -> Option<&'static str> {
+        match self.reply_information {
+            9 => Some("<=75"),
+            10 => Some("75-150"),
+            11 => Some("150-300"),
+            12 => Some("300-600"),
+            13 => Some("600-1200"),
+            14 => Some(">1200"),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if ACAS issues resolution advisories, `false` if it is off, in standby or TA only.
+    pub fn resolution_advisories(&self) -> bool {
+        self.sensitivity_level > 2
+    }
+}
+
+impl Display for AirAirSurveillance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            if self.on_ground { "ground" } else { "airborne" },
+            self.cross_link
+                .map(|v| (v as u32).to_string())
+                .unwrap_or_default(),
+            self.sensitivity_level,
+            self.acas_capability()
+                .or(self.max_airspeed())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Decodes VS (bit 6), CC (bit 7, DF0 only), SL (bits 9-11) and RI (bits 14-17).
+pub(crate) fn air_air_surveillance(message: &[u32], df: u32) -> Option<AirAirSurveillance> {
+    Some(AirAirSurveillance {
+        on_ground: range_value(message, 6, 6)? == 1,
+        cross_link: match df {
+            0 => Some(range_value(message, 7, 7)? == 1),
+            _ => None,
+        },
+        sensitivity_level: range_value(message, 9, 11)?,
+        reply_information: range_value(message, 14, 17)?,
+    })
+}
+
+/// Decodes the MV field (bits 33-88) of a DF16 reply holding an ACAS resolution advisory report (VDS 3,0).
+///
+/// ARA, RAC, RAT, MTE and the threat identity share the bit positions of TC 28 subtype 2,
+/// replies without a current or terminated advisory return `None`.
+pub(crate) fn mv_resolution_advisory(message: &[u32]) -> Option<ResolutionAdvisory> {
+    if range_value(message, 33, 40)? != 0x30 {
+        return None;
+    }
+    resolution_advisory(message).filter(|ra| ra.is_active() || ra.terminated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_air_air_surveillance() {
+        let message = get_message("02E197B00179C3").expect("Failed to get message");
+        let status = air_air_surveillance(&message, 0).expect("Failed to decode DF0");
+        assert!(!status.on_ground);
+        assert_eq!(status.cross_link, Some(true));
+        assert_eq!(status.sensitivity_level, 7);
+        assert_eq!(status.acas_capability(), Some("ACAS vertical RA"));
+        assert!(status.resolution_advisories());
+
+        let message = get_message("02458093AA3F4A").expect("Failed to get message");
+        let status = air_air_surveillance(&message, 0).expect("Failed to decode DF0");
+        assert_eq!(status.max_airspeed(), Some("150-300"));
+        assert!(!status.resolution_advisories());
+    }
+
+    #[test]
+    fn test_mv_resolution_advisory() {
+        let message = get_message("80E1969030C200052103588658DD").expect("Failed to get message");
+        let status = air_air_surveillance(&message, 16).expect("Failed to decode DF16");
+        assert_eq!(status.cross_link, None);
+        let ra = mv_resolution_advisory(&message).expect("Failed to decode RA");
+        assert_eq!(ra.upward(), Some(true));
+        assert_eq!(ra.to_string(), "climb; threat 4840D6");
+
+        let message = get_message("80E1969058B503C886C0348658DD").expect("Failed to get message");
+        assert_eq!(mv_resolution_advisory(&message), None);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::decoder::{get_capability, get_message_type};
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Kind of address and source of a target, from the DF18 control field (CF)
+/// and the ICAO/Mode A flag (IMF) of TIS-B and ADS-R messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressType {
+    /// ADS-B (DF17, DF18 CF0) or Mode S reply from an ICAO address
+    #[default]
+    Icao,
+    /// ADS-B from a non-transponder device with an anonymous or ground vehicle address (CF1)
+    NonIcao,
+    /// TIS-B of a target with an ICAO address (CF2/3)
+    TisbIcao,
+    /// TIS-B of a target with a Mode A code or track file number (CF2/3/5)
+    TisbOther,
+    /// ADS-R rebroadcast of UAT traffic with an ICAO address (CF6)
+    AdsrIcao,
+    /// ADS-R rebroadcast of UAT traffic with a non-ICAO address (CF6)
+    AdsrOther,
+}
+
+impl AddressType {
+    pub fn is_icao(&self) -> bool {
+        matches!(
+            self,
+            AddressType::Icao | AddressType::TisbIcao | AddressType::AdsrIcao
+        )
+    }
+
+    /// One character mark shown next to the address.
+    pub fn marker(&self) -> char {
+        match self {
+            AddressType::Icao => ' ',
+            AddressType::NonIcao => '~',
+            AddressType::TisbIcao => 't',
+            AddressType::TisbOther => 'T',
+            AddressType::AdsrIcao => 'r',
+            AddressType::AdsrOther => 'R',
+        }
+    }
+
+    /// Returns the `Planes` key of an address of this type.
+    ///
+    /// Every type has its own key space above the 24-bit address, so that rebroadcast
+    /// and non-ICAO targets never merge into an aircraft heard directly.
+    pub fn key(&self, address: u32) -> u32 {
+        address | (*self as u32) << 24
+    }
+}
+
+impl Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            AddressType::Icao => "adsb_icao",
+            AddressType::NonIcao => "adsb_other",
+            AddressType::TisbIcao => "tisb_icao",
+            AddressType::TisbOther => "tisb_other",
+            AddressType::AdsrIcao => "adsr_icao",
+            AddressType::AdsrOther => "adsr_other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Returns the address type of a message.
+///
+/// # Arguments
+///
+/// * `message` - The message.
+/// * `df` - The downlink format of the message.
+///
+/// # Returns
+///
+/// The address type, [`AddressType::Icao`] for every downlink format but DF18.
+pub fn address_type(message: &[u32], df: u32) -> AddressType {
+    if df != 18 {
+        return AddressType::Icao;
+    }
+    let imf = imf(message);
+    match (get_capability(message), imf) {
+        (1, _) => AddressType::NonIcao,
+        (2 | 3, false) => AddressType::TisbIcao,
+        (2 | 3, true) | (5, _) => AddressType::TisbOther,
+        (6, false) => AddressType::AdsrIcao,
+        (6, true) => AddressType::AdsrOther,
+        _ => AddressType::Icao,
+    }
+}
+
+/// Returns `true` if a DF18 message uses the DF17 extended squitter formats.
+///
+/// Coarse TIS-B (CF3), management messages (CF4) and the reserved CF7 do not.
+pub(crate) fn is_extended_squitter(message: &[u32]) -> bool {
+    matches!(get_capability(message), 0 | 1 | 2 | 5 | 6)
+}
+
+/// ICAO/Mode A flag of TIS-B and ADS-R messages, set for non-ICAO addresses.
+fn imf(message: &[u32]) -> bool {
+    let me_bit = match get_capability(message) {
+        3 => 1,
+        2 | 6 => match get_message_type(message).0 {
+            5..=8 => 21,
+            9..=18 | 20..=22 => 8,
+            19 => 9,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    range_value(message, 32 + me_bit, 32 + me_bit) == Some(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{DF, Downlink, get_downlink_format, get_message};
+
+    #[test]
+    fn test_address_type() {
+        let squitters = [
+            ("8D40621D58C382D690C8AC2863A7", AddressType::Icao),
+            ("9040621D58C382D690C8AC556F52", AddressType::Icao),
+            ("9140621D58C382D690C8AC0D1E2A", AddressType::NonIcao),
+            ("9240621D58C382D690C8ACE58DA2", AddressType::TisbIcao),
+            ("9240621D59C382D690C8AC39F755", AddressType::TisbOther),
+            ("9540621D58C382D690C8AC932FC3", AddressType::TisbOther),
+            ("9640621D58C382D690C8AC7BBC4B", AddressType::AdsrIcao),
+            ("9640621D99C382D690C8AC78E721", AddressType::AdsrOther),
+        ];
+        for (squitter, expected) in squitters {
+            let message = get_message(squitter).expect("Failed to get message");
+            let df = get_downlink_format(&message).expect("Failed to get DF");
+            assert_eq!(address_type(&message, df), expected, "{}", squitter);
+        }
+    }
+
+    #[test]
+    fn test_coarse_tisb() {
+        for squitter in [
+            "9340621D58C382D690C8ACBDFCDA",
+            "9440621D58C382D690C8ACCB5EBB",
+        ] {
+            let message = get_message(squitter).expect("Failed to get message");
+            assert!(!is_extended_squitter(&message));
+            assert!(DF::from_message(&message).is_err(), "{}", squitter);
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        assert_eq!(AddressType::Icao.key(0x40621D), 0x40621D);
+        assert_eq!(AddressType::TisbIcao.key(0x40621D), 0x240621D);
+        assert_eq!(AddressType::TisbOther.key(0x40621D), 0x340621D);
+        assert_eq!(AddressType::AdsrOther.marker(), 'R');
+    }
+}
@@ -0,0 +1,80 @@
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Emergency state of an emergency/priority status message (TC 28 subtype 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emergency {
+    None,
+    General,
+    Medical,
+    MinimumFuel,
+    NoCommunications,
+    UnlawfulInterference,
+    Downed,
+    Reserved,
+}
+
+impl Emergency {
+    /// Four character abbreviation shown in the table.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Emergency::None => "",
+            Emergency::General => "EMRG",
+            Emergency::Medical => "MED",
+            Emergency::MinimumFuel => "FUEL",
+            Emergency::NoCommunications => "NORD",
+            Emergency::UnlawfulInterference => "HIJK",
+            Emergency::Downed => "DOWN",
+            Emergency::Reserved => "RSVD",
+        }
+    }
+}
+
+impl Display for Emergency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Emergency::None => "no emergency",
+            Emergency::General => "general emergency",
+            Emergency::Medical => "lifeguard/medical emergency",
+            Emergency::MinimumFuel => "minimum fuel",
+            Emergency::NoCommunications => "no communications",
+            Emergency::UnlawfulInterference => "unlawful interference",
+            Emergency::Downed => "downed aircraft",
+            Emergency::Reserved => "reserved",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Decodes the emergency state of TC 28 subtype 1 (ME bits 9-11).
+pub(crate) fn emergency_state(message: &[u32]) -> Option<Emergency> {
+    range_value(message, 41, 43).map(|value| match value {
+        0 => Emergency::None,
+        1 => Emergency::General,
+        2 => Emergency::Medical,
+        3 => Emergency::MinimumFuel,
+        4 => Emergency::NoCommunications,
+        5 => Emergency::UnlawfulInterference,
+        6 => Emergency::Downed,
+        _ => Emergency::Reserved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{emergency_squawk, get_message};
+
+    #[test]
+    fn test_emergency_state() {
+        let squitters = [
+            ("8D4840D6E12AAA000000003CF5CE", Emergency::General, 7700),
+            ("8D4840D6E1000000000000DE52AB", Emergency::None, 0),
+        ];
+        for (squitter, emergency, squawk) in squitters {
+            let message = get_message(squitter).expect("Failed to get message");
+            assert_eq!(emergency_state(&message), Some(emergency));
+            assert_eq!(emergency_squawk(&message), Some(squawk));
+        }
+    }
+}
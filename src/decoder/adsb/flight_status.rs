@@ -0,0 +1,132 @@
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Flight status (FS) of a surveillance reply (DF4/5/20/21).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlightStatus {
+    /// `None` when the reply carries the alert and SPI without the vertical status
+    pub on_ground: Option<bool>,
+    /// The Mode A code has been changed
+    pub alert: bool,
+    /// Special position identification, the pilot pressed IDENT
+    pub spi: bool,
+}
+
+impl Display for FlightStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = [
+            match self.on_ground {
+                Some(true) => "ground",
+                Some(false) => "airborne",
+                None => "",
+            },
+            if self.alert { "alert" } else { "" },
+            if self.spi { "SPI" } else { "" },
+        ];
+        write!(
+            f,
+            "{}",
+            status
+                .iter()
+                .filter(|v| !v.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+/// Downlink request (DR) and utility message (UM) of a surveillance reply (DF4/5/20/21).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownlinkRequest {
+    /// DR, 1 Comm-B, 2 ACAS, 3 Comm-B and ACAS, 4-7 Comm-B broadcast, 16-31 ELM
+    pub request: u32,
+    /// Interrogator identifier subfield (IIS) of the interrogator holding the reservation
+    pub iis: u32,
+    /// Identifier designator subfield (IDS), 1 Comm-B, 2 Comm-C, 3 Comm-D reservation
+    pub ids: u32,
+}
+
+impl DownlinkRequest {
+    /// Number of the Comm-B broadcast message waiting to be read, if any.
+    pub fn comm_b_broadcast(&self) -> Option<u32> {
+        match self.request {
+            4 | 6 => Some(1),
+            5 | 7 => Some(2),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DownlinkRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.request,
+            self.comm_b_broadcast()
+                .map(|v| format!("B{}", v))
+                .unwrap_or_default(),
+            self.iis,
+            self.ids
+        )
+    }
+}
+
+/// Decodes the flight status (bits 6-8), `None` for the unassigned values 6 and 7.
+pub(crate) fn flight_status(message: &[u32]) -> Option<FlightStatus> {
+    let (on_ground, alert, spi) = match range_value(message, 6, 8)? {
+        0 => (Some(false), false, false),
+        1 => (Some(true), false, false),
+        2 => (Some(false), true, false),
+        3 => (Some(true), true, false),
+        4 => (None, true, true),
+        5 => (None, false, true),
+        _ => return None,
+    };
+    Some(FlightStatus {
+        on_ground,
+        alert,
+        spi,
+    })
+}
+
+/// Decodes the downlink request (bits 9-13) and the utility message (bits 14-19).
+pub(crate) fn downlink_request(message: &[u32]) -> Option<DownlinkRequest> {
+    Some(DownlinkRequest {
+        request: range_value(message, 9, 13)?,
+        iis: range_value(message, 14, 17)?,
+        ids: range_value(message, 18, 19)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_flight_status() {
+        let message = get_message("A20015B7E8AA2F327FD7FF0C302B").expect("Failed to get message");
+        let status = flight_status(&message).expect("Failed to get flight status");
+        assert_eq!(
+            (status.on_ground, status.alert, status.spi),
+            (Some(false), true, false)
+        );
+        assert_eq!(status.to_string(), "airborne alert");
+
+        let message = get_message("2D01B89A8E0F41").expect("Failed to get message");
+        let status = flight_status(&message).expect("Failed to get flight status");
+        assert_eq!(
+            (status.on_ground, status.alert, status.spi),
+            (None, false, true)
+        );
+        let request = downlink_request(&message).expect("Failed to get downlink request");
+        assert_eq!((request.request, request.iis, request.ids), (0, 3, 1));
+
+        let message = get_message("A828010A10010080F600001C4F59").expect("Failed to get message");
+        let request = downlink_request(&message).expect("Failed to get downlink request");
+        assert_eq!(request.comm_b_broadcast(), Some(2));
+        assert_eq!(request.to_string(), "5,B2,0,0");
+    }
+}
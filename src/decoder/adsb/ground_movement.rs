@@ -1,14 +1,17 @@
+/// Decodes the ground speed (kt) of a surface position, `None` when not available or reserved.
+///
+/// The encoding is quantised in steps growing with the speed, 1 means stopped and 124 175 kt or more.
 pub(crate) fn ground_movement(message: &[u32]) -> Option<f64> {
     if let Some(value) = crate::range_value(message, 38, 44) {
         let value = value as f64;
         match value {
             1.0 => Some(0.0),
-            2.0..=8.0 => Some(value / 8.0),
-            9.0..=12.0 => Some(value / 4.0),
-            13.0..=38.0 => Some(value / 2.0),
-            39.0..=93.0 => Some(value),
-            94.0..=108.0 => Some(value * 2.0),
-            109.0..=123.0 => Some(value * 5.0),
+            2.0..=8.0 => Some(0.125 + (value - 2.0) * 0.125),
+            9.0..=12.0 => Some(1.0 + (value - 9.0) * 0.25),
+            13.0..=38.0 => Some(2.0 + (value - 13.0) * 0.5),
+            39.0..=93.0 => Some(15.0 + (value - 39.0)),
+            94.0..=108.0 => Some(70.0 + (value - 94.0) * 2.0),
+            109.0..=123.0 => Some(100.0 + (value - 109.0) * 5.0),
             124.0 => Some(175.0),
             _ => None,
         }
@@ -16,3 +19,17 @@ pub(crate) fn ground_movement(message: &[u32]) -> Option<f64> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_ground_movement() {
+        let message = get_message("8C4841753A9A153237AEF0F275BE").expect("Failed to get message");
+        assert_eq!(ground_movement(&message), Some(17.0));
+        let message = get_message("8C4841753AAB238733C8CD4020B1").expect("Failed to get message");
+        assert_eq!(ground_movement(&message), Some(18.0));
+    }
+}
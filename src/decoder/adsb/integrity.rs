@@ -0,0 +1,156 @@
+use crate::range_value;
+use std::fmt::{self, Display};
+
+const NM: f64 = 1852.0;
+
+/// Position integrity of the last position message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionIntegrity {
+    /// NIC for ADS-B versions 1 and 2, NUCp for version 0
+    pub nic: u32,
+    /// `true` when `nic` holds a NUCp
+    pub nuc: bool,
+    /// Containment radius (m), `None` when unknown
+    pub rc: Option<f64>,
+    /// Navigation accuracy and source integrity from the operational status
+    pub nac_p: Option<u32>,
+    pub sil: Option<u32>,
+}
+
+impl Display for PositionIntegrity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>2}{}",
+            self.nic,
+            if self.nuc { '\u{1d58}' } else { ' ' }
+        )
+    }
+}
+
+/// NIC supplement bits, A and C from the operational status, B from the airborne position.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NicSupplements {
+    pub a: bool,
+    pub b: bool,
+    pub c: bool,
+}
+
+/// Reads the NIC supplement B (ME bit 8) of an airborne position.
+pub(crate) fn nic_supplement_b(message: &[u32]) -> bool {
+    range_value(message, 40, 40) == Some(1)
+}
+
+/// Derives NUCp (version 0) or NIC and the containment radius from the position type code.
+///
+/// The version is unknown until an operational status is received, version 0 is assumed.
+pub(crate) fn position_integrity(
+    type_code: u32,
+    version: Option<u32>,
+    supplements: NicSupplements,
+    nac_p: Option<u32>,
+    sil: Option<u32>,
+) -> Option<PositionIntegrity> {
+    let version = version.unwrap_or(0);
+    let NicSupplements { a, b, c } = supplements;
+    let (nic, rc) = match (version, type_code) {
+        (0, _) => nuc_p(type_code)?,
+        // Surface position
+        (_, 5) => (11, Some(7.5)),
+        (_, 6) => (10, Some(25.0)),
+        (1, 7) | (2.., 7) if a => (9, Some(75.0)),
+        (_, 7) => (8, Some(0.1 * NM)),
+        (2.., 8) => match (a, c) {
+            (true, true) => (7, Some(0.2 * NM)),
+            (true, false) => (6, Some(0.3 * NM)),
+            (false, true) => (6, Some(0.6 * NM)),
+            (false, false) => (0, None),
+        },
+        (_, 8) => (0, None),
+        // Airborne position
+        (_, 9) | (_, 20) => (11, Some(7.5)),
+        (_, 10) | (_, 21) => (10, Some(25.0)),
+        (1, 11) if a => (9, Some(75.0)),
+        (2.., 11) if a && b => (9, Some(75.0)),
+        (_, 11) => (8, Some(0.1 * NM)),
+        (_, 12) => (7, Some(0.2 * NM)),
+        (1, 13) if a => (6, Some(0.6 * NM)),
+        (2.., 13) if !a && b => (6, Some(0.3 * NM)),
+        (2.., 13) if a && b => (6, Some(0.6 * NM)),
+        (_, 13) => (6, Some(0.5 * NM)),
+        (_, 14) => (5, Some(NM)),
+        (_, 15) => (4, Some(2.0 * NM)),
+        (1, 16) if a => (3, Some(4.0 * NM)),
+        (2.., 16) if a && b => (3, Some(4.0 * NM)),
+        (_, 16) => (2, Some(8.0 * NM)),
+        (_, 17) => (1, Some(20.0 * NM)),
+        (_, 18) | (_, 22) => (0, None),
+        _ => return None,
+    };
+    Some(PositionIntegrity {
+        nic,
+        nuc: version == 0,
+        rc,
+        nac_p,
+        sil,
+    })
+}
+
+/// NUCp and horizontal protection limit (m) of ADS-B version 0.
+fn nuc_p(type_code: u32) -> Option<(u32, Option<f64>)> {
+    let nuc = match type_code {
+        5 | 9 | 20 => 9,
+        6 | 10 | 21 => 8,
+        7 | 11 => 7,
+        8 | 12 => 6,
+        13..=17 => 18 - type_code,
+        18 | 22 => 0,
+        _ => return None,
+    };
+    let hpl = match nuc {
+        9 => Some(7.5),
+        8 => Some(25.0),
+        7 => Some(0.1 * NM),
+        6 => Some(0.2 * NM),
+        5 => Some(0.5 * NM),
+        4 => Some(NM),
+        3 => Some(2.0 * NM),
+        2 => Some(10.0 * NM),
+        1 => Some(20.0 * NM),
+        _ => None,
+    }
+    // Surface positions of type code 8 only have an HPL of at least 0.1 NM
+    .filter(|_| type_code != 8);
+    Some((nuc, hpl))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_integrity() {
+        let none = NicSupplements::default();
+        let ab = NicSupplements {
+            a: true,
+            b: true,
+            c: false,
+        };
+        let nic = |type_code, version, supplements| {
+            position_integrity(type_code, version, supplements, None, None)
+                .map(|integrity| (integrity.nic, integrity.nuc, integrity.rc.map(f64::round)))
+        };
+        assert_eq!(nic(11, None, none), Some((7, true, Some(185.0))));
+        assert_eq!(nic(11, Some(1), none), Some((8, false, Some(185.0))));
+        assert_eq!(nic(11, Some(2), ab), Some((9, false, Some(75.0))));
+        assert_eq!(nic(16, Some(2), ab), Some((3, false, Some(7408.0))));
+        assert_eq!(nic(13, Some(2), none), Some((6, false, Some(926.0))));
+        assert_eq!(nic(18, Some(2), none), Some((0, false, None)));
+        assert_eq!(nic(7, Some(1), ab), Some((9, false, Some(75.0))));
+        assert_eq!(nic(19, Some(2), none), None);
+        assert_eq!(nic(5, None, none), Some((9, true, Some(8.0))));
+        assert_eq!(nic(6, None, none), Some((8, true, Some(25.0))));
+        assert_eq!(nic(7, None, none), Some((7, true, Some(185.0))));
+        assert_eq!(nic(8, None, none), Some((6, true, None)));
+    }
+}
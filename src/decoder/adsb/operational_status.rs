@@ -0,0 +1,288 @@
+use super::version;
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Aircraft length and width (m) by length/width code, 0 is no data.
+const DIMENSIONS: [(f64, f64); 16] = [
+    (0.0, 0.0),
+    (15.0, 23.0),
+    (25.0, 28.5),
+    (25.0, 34.0),
+    (35.0, 33.0),
+    (35.0, 38.0),
+    (45.0, 39.5),
+    (45.0, 45.0),
+    (55.0, 45.0),
+    (55.0, 52.0),
+    (65.0, 59.5),
+    (65.0, 67.0),
+    (75.0, 72.5),
+    (75.0, 80.0),
+    (85.0, 80.0),
+    (85.0, 90.0),
+];
+
+/// Aircraft operational status (TC 31), fields not defined by the ADS-B version are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OperationalStatus {
+    pub version: u32,
+    /// Surface (subtype 1) or airborne (subtype 0) status
+    pub surface: bool,
+    /// Capability class codes
+    pub tcas_operational: Option<bool>,
+    pub cdti: Option<bool>,
+    pub es_in: Option<bool>,
+    pub uat_in: Option<bool>,
+    pub arv: Option<bool>,
+    pub ts: Option<bool>,
+    pub tc: Option<u32>,
+    pub poa: Option<bool>,
+    pub b2_low: Option<bool>,
+    pub nac_v: Option<u32>,
+    pub nic_supplement_c: Option<bool>,
+    /// Operational mode codes
+    pub ra_active: Option<bool>,
+    pub ident: Option<bool>,
+    pub atc_services: Option<bool>,
+    pub single_antenna: Option<bool>,
+    pub sda: Option<u32>,
+    /// Lateral (m, negative left) and longitudinal (m aft of the nose) GPS antenna offset
+    pub gps_antenna_offset: Option<(Option<i32>, Option<u32>)>,
+    pub length_width: Option<u32>,
+    /// Integrity and accuracy
+    pub nic_supplement_a: Option<bool>,
+    pub nac_p: Option<u32>,
+    pub gva: Option<u32>,
+    pub sil: Option<u32>,
+    /// SIL probability per sample (`true`) or per hour
+    pub sil_supplement: Option<bool>,
+    pub nic_baro: Option<bool>,
+    /// Horizontal reference direction, `true` for magnetic north
+    pub hrd: Option<bool>,
+    /// Surface track angle (`false`) or heading (`true`) reported
+    pub track_heading: Option<bool>,
+}
+
+impl OperationalStatus {
+    /// Upper bounds of the aircraft length and width (m).
+    pub fn dimensions(&self) -> Option<(f64, f64)> {
+        self.length_width
+            .filter(|&code| code != 0)
+            .map(|code| DIMENSIONS[code as usize])
+    }
+
+    fn capabilities(&self) -> String {
+        [
+            (self.tcas_operational, 'T'),
+            (self.cdti, 'C'),
+            (self.es_in, 'E'),
+            (self.uat_in, 'U'),
+            (self.arv, 'A'),
+            (self.ts, 'S'),
+            (self.poa, 'P'),
+            (self.b2_low, 'B'),
+        ]
+        .iter()
+        .filter(|(flag, _)| *flag == Some(true))
+        .map(|(_, c)| *c)
+        .collect()
+    }
+
+    fn modes(&self) -> String {
+        [
+            (self.ra_active, 'R'),
+            (self.ident, 'I'),
+            (self.atc_services, 'A'),
+            (self.single_antenna, 'S'),
+        ]
+        .iter()
+        .filter(|(flag, _)| *flag == Some(true))
+        .map(|(_, c)| *c)
+        .collect()
+    }
+}
+
+impl Display for OperationalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn field<T: Display>(f: &mut fmt::Formatter, value: Option<T>) -> fmt::Result {
+            match value {
+                Some(v) => write!(f, ",{}", v),
+                None => write!(f, ","),
+            }
+        }
+        write!(f, "{}", self.capabilities())?;
+        write!(f, ",{}", self.modes())?;
+        field(f, self.nic_supplement_a.map(u32::from))?;
+        field(f, self.nac_p)?;
+        field(f, self.gva)?;
+        field(f, self.sil)?;
+        field(f, self.nic_baro.map(u32::from))?;
+        field(f, self.length_width)
+    }
+}
+
+/// Decodes the aircraft operational status message for ADS-B versions 0, 1 and 2.
+pub(crate) fn operational_status(message: &[u32]) -> Option<OperationalStatus> {
+    let subtype = range_value(message, 38, 40)?;
+    if subtype > 1 {
+        return None;
+    }
+    let version = version(message)?;
+    let surface = subtype == 1;
+    let flag = |bit| range_value(message, bit, bit).map(|v| v == 1);
+    let mut status = OperationalStatus {
+        version,
+        surface,
+        ..Default::default()
+    };
+
+    if version == 0 {
+        // En route capabilities, only the TCAS bit is in use
+        status.tcas_operational = flag(43).map(|not_tcas| !not_tcas);
+        return Some(status);
+    }
+
+    // Capability class codes with a service level other than 0 are reserved
+    if range_value(message, 41, 42)? == 0 {
+        match (version, surface) {
+            (1, false) => {
+                status.tcas_operational = flag(43).map(|not_tcas| !not_tcas);
+                status.cdti = flag(44);
+                status.arv = flag(47);
+                status.ts = flag(48);
+                status.tc = range_value(message, 49, 50);
+            }
+            (1, true) => {
+                status.poa = flag(43);
+                status.cdti = flag(44);
+                status.b2_low = flag(45);
+            }
+            (_, false) => {
+                status.tcas_operational = flag(43);
+                status.es_in = flag(44);
+                status.arv = flag(47);
+                status.ts = flag(48);
+                status.tc = range_value(message, 49, 50);
+                status.uat_in = flag(51);
+            }
+            (_, true) => {
+                status.poa = flag(43);
+                status.es_in = flag(44);
+                status.b2_low = flag(47);
+                status.uat_in = flag(48);
+                status.nac_v = range_value(message, 49, 51);
+                status.nic_supplement_c = flag(52);
+            }
+        }
+    }
+    if surface {
+        status.length_width = range_value(message, 53, 56);
+    }
+
+    // Operational mode codes with a format other than 0 are reserved
+    if range_value(message, 57, 58)? == 0 {
+        status.ra_active = flag(59);
+        status.ident = flag(60);
+        status.atc_services = flag(61);
+        if version >= 2 {
+            status.single_antenna = flag(62);
+            status.sda = range_value(message, 63, 64);
+            if surface {
+                status.gps_antenna_offset = Some((
+                    range_value(message, 65, 67).and_then(lateral_offset),
+                    range_value(message, 68, 72).and_then(longitudinal_offset),
+                ));
+            }
+        }
+    }
+
+    status.nic_supplement_a = flag(76);
+    status.nac_p = range_value(message, 77, 80);
+    if version >= 2 && !surface {
+        status.gva = range_value(message, 81, 82);
+    }
+    status.sil = range_value(message, 83, 84);
+    if surface {
+        status.track_heading = flag(85);
+    } else {
+        status.nic_baro = flag(85);
+    }
+    status.hrd = flag(86);
+    if version >= 2 {
+        status.sil_supplement = flag(87);
+    }
+    Some(status)
+}
+
+/// Lateral GPS antenna offset in 2 m steps, left negative.
+fn lateral_offset(code: u32) -> Option<i32> {
+    match code {
+        0 => None,
+        1..=3 => Some(-2 * code as i32),
+        _ => Some(2 * (code as i32 - 4)),
+    }
+}
+
+/// Longitudinal GPS antenna offset in 2 m steps aft of the nose, 0 when applied by the sensor.
+fn longitudinal_offset(code: u32) -> Option<u32> {
+    match code {
+        0 => None,
+        1 => Some(0),
+        _ => Some((code - 1) * 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    fn status(squitter: &str) -> OperationalStatus {
+        let message = get_message(squitter).expect("Failed to get message");
+        operational_status(&message).expect("Failed to decode operational status")
+    }
+
+    #[test]
+    fn test_airborne_v2() {
+        let status = status("8D4CADC2F8230006004AB8E0D264");
+        assert_eq!((status.version, status.surface), (2, false));
+        assert_eq!(status.tcas_operational, Some(true));
+        assert_eq!(status.es_in, Some(false));
+        assert_eq!((status.arv, status.ts), (Some(true), Some(true)));
+        assert_eq!(status.single_antenna, Some(true));
+        assert_eq!(status.sda, Some(2));
+        assert_eq!(status.nac_p, Some(10));
+        assert_eq!(status.gva, Some(2));
+        assert_eq!(status.sil, Some(3));
+        assert_eq!(status.nic_baro, Some(true));
+        assert_eq!(status.hrd, Some(false));
+        assert_eq!(status.to_string(), "TAS,S,0,10,2,3,1,");
+    }
+
+    #[test]
+    fn test_airborne_v1() {
+        let status = status("8D4840D6F810002000382C5B163E");
+        assert_eq!((status.version, status.surface), (1, false));
+        assert_eq!(status.tcas_operational, Some(true));
+        assert_eq!(status.cdti, Some(true));
+        assert_eq!(status.ra_active, Some(true));
+        assert_eq!(status.nic_supplement_a, Some(true));
+        assert_eq!(status.nac_p, Some(8));
+        assert_eq!((status.gva, status.sil_supplement), (None, None));
+        assert_eq!(status.sil, Some(2));
+        assert_eq!((status.nic_baro, status.hrd), (Some(true), Some(true)));
+    }
+
+    #[test]
+    fn test_surface_v2() {
+        let status = status("8D4840D6F9304902A749380FFDE1");
+        assert_eq!((status.version, status.surface), (2, true));
+        assert_eq!((status.poa, status.es_in), (Some(true), Some(true)));
+        assert_eq!(status.nac_v, Some(2));
+        assert_eq!(status.dimensions(), Some((55.0, 52.0)));
+        assert_eq!(status.gps_antenna_offset, Some((Some(2), Some(12))));
+        assert_eq!(status.nac_p, Some(9));
+        assert_eq!((status.gva, status.nic_baro), (None, None));
+        assert_eq!(status.track_heading, Some(true));
+    }
+}
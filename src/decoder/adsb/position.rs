@@ -10,7 +10,6 @@ pub(crate) fn cpr_location(
     cpr_lat: &[u32; 2],
     cpr_lon: &[u32; 2],
     cpr_form: u32,
-    coeff: i32,
 ) -> Option<(f64, f64)> {
     let div = (1 << 17) as f64;
     let adl0 = 6.0; // 360 / 60
@@ -26,23 +25,15 @@ pub(crate) fn cpr_location(
     match nl[0] == nl[1] {
         true => {
             let (ni, nlt, lngt) = match cpr_form {
-                1 => (
-                    *[nl[1] / coeff - 1, 1].iter().max()?, // coeff = 4 for ground location
-                    nl[1] / coeff,                         // coeff = 1 for airborne location
-                    cpr_lon[1],
-                ),
-                _ => (
-                    *[nl[0] / coeff, 1].iter().max()?, // coeff = 4 for ground location
-                    nl[0] / coeff,                     // coeff = 1 for airborne location
-                    cpr_lon[0],
-                ),
+                1 => (*[nl[1] - 1, 1].iter().max()?, nl[1], cpr_lon[1]),
+                _ => (*[nl[0], 1].iter().max()?, nl[0], cpr_lon[0]),
             };
             let dlngt = 360.0 / ni as f64;
             let m = (((cpr_lon[0] as f64 * (nlt - 1) as f64 - cpr_lon[1] as f64 * nlt as f64)
                 / div)
                 + 0.5)
                 .floor();
-            let lon = dlngt * (pmod(m as i32, ni) as f64 + lngt as f64 / div) as f64;
+            let lon = dlngt * (pmod(m as i32, ni) as f64 + lngt as f64 / div);
 
             Some((rlat[cpr_form as usize], signed_lon(lon)))
         }
@@ -50,6 +41,92 @@ pub(crate) fn cpr_location(
     }
 }
 
+/// Decodes a surface position from the even and odd CPR formats.
+///
+/// Surface positions are encoded in 90° zones, the decoding yields one latitude per hemisphere
+/// and four longitudes 90° apart. The solution closest to the reference position is returned.
+///
+/// # Arguments
+///
+/// * `reference` - The latitude and longitude of the receiver or of the last known aircraft position.
+/// * `cpr_lat` - The even and odd encoded latitudes.
+/// * `cpr_lon` - The even and odd encoded longitudes.
+/// * `cpr_form` - The CPR format of the most recent message, 0 even and 1 odd.
+pub(crate) fn cpr_surface_location(
+    reference: (f64, f64),
+    cpr_lat: &[u32; 2],
+    cpr_lon: &[u32; 2],
+    cpr_form: u32,
+) -> Option<(f64, f64)> {
+    let div = (1 << 17) as f64;
+    let (lat_ref, lon_ref) = reference;
+    let form = cpr_form as usize;
+
+    let j = ((59.0 * cpr_lat[0] as f64 - 60.0 * cpr_lat[1] as f64) / div + 0.5).floor();
+    let mut rlat = [
+        90.0 / 60.0 * (j.rem_euclid(60.0) + cpr_lat[0] as f64 / div),
+        90.0 / 59.0 * (j.rem_euclid(59.0) + cpr_lat[1] as f64 / div),
+    ];
+    // The northern solution, the southern one lies 90° below
+    if (rlat[form] - 90.0 - lat_ref).abs() < (rlat[form] - lat_ref).abs() {
+        rlat = [rlat[0] - 90.0, rlat[1] - 90.0];
+    }
+
+    let nl = [nl(rlat[0]), nl(rlat[1])];
+    if nl[0] != nl[1] {
+        return None;
+    }
+    let ni = (nl[form] - cpr_form as i32).max(1);
+    let m = ((cpr_lon[0] as f64 * (nl[form] - 1) as f64 - cpr_lon[1] as f64 * nl[form] as f64)
+        / div
+        + 0.5)
+        .floor();
+    let lon = 90.0 / ni as f64 * (pmod(m as i32, ni) as f64 + cpr_lon[form] as f64 / div);
+    let lon = (0..4)
+        .map(|quadrant| signed_lon(lon + 90.0 * quadrant as f64))
+        .min_by(|a, b| lon_distance(*a, lon_ref).total_cmp(&lon_distance(*b, lon_ref)))?;
+
+    Some((rlat[form], lon))
+}
+
+/// Decodes a single CPR position relative to a reference position less than 180 NM away,
+/// or 45 NM for surface positions.
+///
+/// # Arguments
+///
+/// * `reference` - The latitude and longitude of the receiver or of the last known aircraft position.
+/// * `cpr_form` - The CPR format, 0 even and 1 odd.
+/// * `cpr_lat` - The encoded latitude.
+/// * `cpr_lon` - The encoded longitude.
+/// * `surface` - The position is a surface position encoded in 90° zones.
+pub(crate) fn cpr_local_location(
+    reference: (f64, f64),
+    cpr_form: u32,
+    cpr_lat: u32,
+    cpr_lon: u32,
+    surface: bool,
+) -> Option<(f64, f64)> {
+    let div = (1 << 17) as f64;
+    let (lat_ref, lon_ref) = reference;
+    let span = if surface { 90.0 } else { 360.0 };
+
+    let dlat = span / (60 - cpr_form) as f64;
+    let yz = cpr_lat as f64 / div;
+    let j = (lat_ref / dlat).floor() + ((lat_ref.rem_euclid(dlat) / dlat) - yz + 0.5).floor();
+    let lat = dlat * (j + yz);
+    if !(-90.0..=90.0).contains(&lat) {
+        return None;
+    }
+
+    let ni = (nl(lat) - cpr_form as i32).max(1);
+    let dlon = span / ni as f64;
+    let xz = cpr_lon as f64 / div;
+    let m = (lon_ref / dlon).floor() + ((lon_ref.rem_euclid(dlon) / dlon) - xz + 0.5).floor();
+    let lon = dlon * (m + xz);
+
+    Some((lat, signed_lon(lon)))
+}
+
 fn signed_lon(lon: f64) -> f64 {
     match lon {
         180.0.. => lon - 360.0,
@@ -58,6 +135,11 @@ fn signed_lon(lon: f64) -> f64 {
     }
 }
 
+/// Angular distance between two longitudes.
+fn lon_distance(lon: f64, lon_ref: f64) -> f64 {
+    ((lon - lon_ref + 180.0).rem_euclid(360.0) - 180.0).abs()
+}
+
 fn fixed_lat(lat: f64) -> f64 {
     match lat {
         90.0.. => lat - 360.0,
@@ -160,6 +242,44 @@ fn nl(lat: f64) -> i32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cpr_local_location() {
+        let message = crate::decoder::get_message("8D40621D58C382D690C8AC2863A7")
+            .expect("Failed to get message");
+        let (cpr_form, cpr_lat, cpr_lon) = cpr(&message).expect("Failed to get CPR");
+        let (lat, lon) = cpr_local_location((52.258, 3.918), cpr_form, cpr_lat, cpr_lon, false)
+            .expect("Failed to decode position");
+        assert!((lat - 52.25720).abs() < 1e-4, "{}", lat);
+        assert!((lon - 3.91937).abs() < 1e-4, "{}", lon);
+
+        // Any reference within half a zone (3 degrees) of the aircraft
+        let (lat, _) = cpr_local_location((50.0, 3.0), cpr_form, cpr_lat, cpr_lon, false)
+            .expect("Failed to decode position");
+        assert!((lat - 52.25720).abs() < 1e-4, "{}", lat);
+    }
+
+    #[test]
+    fn test_cpr_surface_location() {
+        let cpr_lat = [115609, 39199];
+        let cpr_lon = [116941, 110269];
+        let (lat, lon) = cpr_surface_location((51.990, 4.375), &cpr_lat, &cpr_lon, 1)
+            .expect("Failed to decode position");
+        assert!((lat - 52.32061).abs() < 1e-4, "{}", lat);
+        assert!((lon - 4.73473).abs() < 1e-4, "{}", lon);
+
+        // The same frames seen from the other side of the equator and the date line
+        let (lat, lon) = cpr_surface_location((-37.0, -175.0), &cpr_lat, &cpr_lon, 1)
+            .expect("Failed to decode position");
+        assert!((lat + 37.67939).abs() < 1e-4, "{}", lat);
+        assert!((lon + 174.44096).abs() < 1e-4, "{}", lon);
+
+        // The same odd frame decoded locally
+        let (lat, lon) = cpr_local_location((51.990, 4.375), 1, cpr_lat[1], cpr_lon[1], true)
+            .expect("Failed to decode position");
+        assert!((lat - 52.32061).abs() < 1e-4, "{}", lat);
+        assert!((lon - 4.73473).abs() < 1e-4, "{}", lon);
+    }
+
     #[test]
     fn test_pmod() {
         let x = -5;
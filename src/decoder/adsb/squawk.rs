@@ -1,4 +1,4 @@
-use crate::decoder::ma_code;
+use crate::decoder::{ma_code, ma_code_at};
 
 /// Converts a Mode S squawk code from an ADS-B message into a decimal value.
 ///
@@ -12,13 +12,20 @@ use crate::decoder::ma_code;
 /// * `None` - If the squawk code does not exist in the message.
 ///
 pub(crate) fn squawk(message: &[u32]) -> Option<u32> {
-    ma_code(message).map(|code| {
-        ((((code >> 8) & 1) << 2) | (((code >> 10) & 1) << 1) | ((code >> 12) & 1)) as u32 * 1000
-            + ((((code >> 3) & 1) << 2) | (((code >> 5) & 1) << 1) | ((code >> 7) & 1)) as u32 * 100
-            + ((((code >> 9) & 1) << 2) | (((code >> 11) & 1) << 1) | ((code >> 13) & 1)) as u32
-                * 10
-            + ((((code >> 2) & 1) << 2) | (((code >> 4) & 1) << 1) | ((code >> 6) & 1)) as u32
-    })
+    ma_code(message).map(identity)
+}
+
+/// Converts the Mode A code of an emergency/priority status message (TC 28 subtype 1).
+pub(crate) fn emergency_squawk(message: &[u32]) -> Option<u32> {
+    // ME bits 12-24 are 24 bits after the identity field of DF5/21
+    ma_code_at(message, 6).map(identity)
+}
+
+fn identity(code: u16) -> u32 {
+    ((((code >> 8) & 1) << 2) | (((code >> 10) & 1) << 1) | ((code >> 12) & 1)) as u32 * 1000
+        + ((((code >> 3) & 1) << 2) | (((code >> 5) & 1) << 1) | ((code >> 7) & 1)) as u32 * 100
+        + ((((code >> 9) & 1) << 2) | (((code >> 11) & 1) << 1) | ((code >> 13) & 1)) as u32 * 10
+        + ((((code >> 2) & 1) << 2) | (((code >> 4) & 1) << 1) | ((code >> 6) & 1)) as u32
 }
 
 #[cfg(test)]
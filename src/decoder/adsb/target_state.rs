@@ -0,0 +1,168 @@
+use crate::range_value;
+use std::fmt::{self, Display};
+
+/// Autopilot and navigation modes, `None` when the mode is not reported.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AutopilotModes {
+    pub autopilot: Option<bool>,
+    pub vnav: Option<bool>,
+    pub altitude_hold: Option<bool>,
+    pub approach: Option<bool>,
+    pub lnav: Option<bool>,
+    pub tcas: Option<bool>,
+}
+
+impl Display for AutopilotModes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (mode, c) in [
+            (self.autopilot, 'A'),
+            (self.vnav, 'V'),
+            (self.altitude_hold, 'H'),
+            (self.approach, 'P'),
+            (self.lnav, 'L'),
+            (self.tcas, 'T'),
+        ] {
+            write!(f, "{}", if mode == Some(true) { c } else { ' ' })?;
+        }
+        Ok(())
+    }
+}
+
+/// Target state and status (TC 29).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetState {
+    /// Selected (v2) or target (v1) altitude (ft)
+    pub selected_altitude: Option<u32>,
+    /// Source of the selected altitude, as in BDS 4.0: 1 aircraft altitude, 2 MCP/FCU, 3 FMS
+    pub target_altitude_source: Option<u32>,
+    /// Barometric pressure setting (mb)
+    pub barometric_pressure_setting: Option<u32>,
+    /// Selected (v2) or target (v1) heading/track (degrees)
+    pub selected_heading: Option<u32>,
+    pub autopilot_modes: Option<AutopilotModes>,
+}
+
+impl Display for TargetState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(v) = self.selected_altitude {
+            write!(f, "{}", v)?
+        }
+        if let Some(v) = self.target_altitude_source {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.barometric_pressure_setting {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.selected_heading {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.autopilot_modes {
+            write!(f, ",{}", v.to_string().replace(' ', ""))
+        } else {
+            write!(f, ",")
+        }
+    }
+}
+
+/// Decodes the target state and status message, subtype 0 (ADS-B v1) and subtype 1 (ADS-B v2).
+pub(crate) fn target_state(message: &[u32]) -> Option<TargetState> {
+    match range_value(message, 38, 39)? {
+        0 => target_state_v1(message),
+        1 => target_state_v2(message),
+        _ => None,
+    }
+}
+
+fn target_state_v1(message: &[u32]) -> Option<TargetState> {
+    let vertical_source = range_value(message, 40, 41)?;
+    let horizontal_source = range_value(message, 58, 59)?;
+    Some(TargetState {
+        // Target altitude in 100 ft increments from -1000 ft
+        selected_altitude: range_value(message, 48, 57)
+            .filter(|_| vertical_source != 0)
+            .filter(|&n| (10..=1010).contains(&n))
+            .map(|n| n * 100 - 1000),
+        // Autopilot control panel, holding altitude, FMS/RNAV
+        target_altitude_source: match vertical_source {
+            1 => Some(2),
+            2 => Some(1),
+            3 => Some(3),
+            _ => None,
+        },
+        barometric_pressure_setting: None,
+        selected_heading: range_value(message, 60, 68)
+            .filter(|_| horizontal_source != 0)
+            .filter(|&heading| heading < 360),
+        autopilot_modes: None,
+    })
+}
+
+fn target_state_v2(message: &[u32]) -> Option<TargetState> {
+    let flag = |bit| range_value(message, bit, bit).map(|v| v == 1);
+    let mode_status = flag(79)?;
+    let mode = |bit| flag(bit).filter(|_| mode_status);
+    Some(TargetState {
+        selected_altitude: range_value(message, 42, 52)
+            .filter(|&n| n != 0)
+            .map(|n| (n - 1) * 32),
+        target_altitude_source: Some(if flag(41)? { 3 } else { 2 }),
+        barometric_pressure_setting: range_value(message, 53, 61)
+            .filter(|&n| n != 0)
+            .map(|n| ((n - 1) as f64 * 0.8 + 800.0).round() as u32),
+        selected_heading: range_value(message, 63, 71)
+            .filter(|_| flag(62) == Some(true))
+            .map(|n| (n as f64 * 180.0 / 256.0).round() as u32 % 360),
+        autopilot_modes: Some(AutopilotModes {
+            autopilot: mode(80),
+            vnav: mode(81),
+            altitude_hold: mode(82),
+            approach: mode(84),
+            lnav: mode(86),
+            tcas: flag(85),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_target_state_v2() {
+        let message = get_message("8DA05629EA21485CBF3F8CADAEEB").expect("Failed to get message");
+        let target_state = target_state(&message).expect("Failed to decode target state");
+        assert_eq!(target_state.selected_altitude, Some(16992));
+        assert_eq!(target_state.target_altitude_source, Some(2));
+        assert_eq!(target_state.barometric_pressure_setting, Some(1013));
+        assert_eq!(target_state.selected_heading, Some(67));
+        assert_eq!(
+            target_state.autopilot_modes.map(|modes| modes.to_string()),
+            Some("AV  LT".to_string())
+        );
+    }
+
+    #[test]
+    fn test_target_state_v1() {
+        let message = get_message("8DA362A2E888B427D0F810288408").expect("Failed to get message");
+        let target_state = target_state(&message).expect("Failed to decode target state");
+        assert_eq!(target_state.selected_altitude, Some(35000));
+        assert_eq!(target_state.target_altitude_source, Some(2));
+        assert_eq!(target_state.barometric_pressure_setting, None);
+        assert_eq!(target_state.selected_heading, Some(125));
+        assert_eq!(target_state.autopilot_modes, None);
+
+        // Horizontal data only
+        let message = get_message("8DA9FA5CE80000258138005D0362").expect("Failed to get message");
+        let horizontal = super::target_state(&message).expect("Failed to decode target state");
+        assert_eq!(horizontal.selected_altitude, None);
+        assert_eq!(horizontal.target_altitude_source, None);
+        assert_eq!(horizontal.selected_heading, Some(88));
+    }
+}
@@ -1,16 +1,33 @@
+mod bds_1_0;
 mod bds_1_7;
+mod bds_2_1;
+mod bds_2_5;
+mod bds_3_0;
 mod bds_4_0;
+mod bds_4_1;
+mod bds_4_2;
+mod bds_4_3;
 mod bds_4_4;
 mod bds_4_5;
 mod bds_5_0;
 mod bds_6_0;
+mod inference;
 
+pub(crate) use bds_1_0::*;
 pub(crate) use bds_1_7::*;
+pub(crate) use bds_2_1::*;
+pub(crate) use bds_2_5::*;
+pub(crate) use bds_3_0::*;
 pub(crate) use bds_4_0::*;
+pub(crate) use bds_4_1::*;
+pub(crate) use bds_4_2::*;
+pub(crate) use bds_4_3::*;
 pub(crate) use bds_4_4::*;
 pub(crate) use bds_4_5::*;
 pub(crate) use bds_5_0::*;
 pub(crate) use bds_6_0::*;
+pub use inference::{BdsStatistics, bds_statistics};
+pub(crate) use inference::{CommB, KnownState, infer_bds, record_inference};
 
 use super::{flag_and_range_value, range_value};
 
@@ -35,6 +52,10 @@ pub fn bds(message: &[u32]) -> (u32, u32) {
         return (2, 0);
     };
 
+    if let (2, 5) = (message[8] & 0xF, message[9] & 0xF) {
+        return (2, 5);
+    };
+
     if let (3, 0) = (message[8] & 0xF, message[9] & 0xF)
         && let Some(value) = range_value(message, 48, 54)
         && (message[15] & 0b1100) != 0b1100
@@ -46,6 +67,20 @@ pub fn bds(message: &[u32]) -> (u32, u32) {
     (0, 0)
 }
 
+/// Decodes `count` 6-bit characters starting at bit `start`, `None` if any is outside
+/// the letters, digits, space and hyphen of the ICAO character set.
+pub(crate) fn characters(message: &[u32], start: u32, count: u32) -> Option<String> {
+    (0..count)
+        .map(
+            |i| match range_value(message, start + i * 6, start + i * 6 + 5)? {
+                c @ 1..=26 => char::from_u32(c | 64),
+                c @ (32 | 45 | 48..=57) => char::from_u32(c),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
 pub(crate) fn goodflags(message: &[u32], flag: u32, sb: u32, eb: u32) -> bool {
     match flag_and_range_value(message, flag, sb, eb) {
         Some((flag, result)) => match flag {
@@ -0,0 +1,130 @@
+use crate::decoder;
+use std::fmt::{self, Display};
+
+/// Data link capability report (BDS 1.0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataLinkCapability {
+    /// Continuation flag, the next register (1.1) holds the rest of the report
+    pub continuation: bool,
+    /// Overlay command capability (OCC)
+    pub overlay_command: bool,
+    /// ACAS operating, `false` when failed or on standby
+    pub acas: bool,
+    /// Mode S subnetwork version number, 0 when the subnetwork is not available
+    pub subnetwork_version: u32,
+    /// Transponder enhanced protocol indicator, `true` for level 5, `false` for levels 2 to 4
+    pub enhanced_protocol: bool,
+    /// Mode S specific services capability
+    pub specific_services: bool,
+    /// Uplink ELM average throughput capability
+    pub uplink_elm: u32,
+    /// Downlink ELM throughput capability
+    pub downlink_elm: u32,
+    /// Aircraft identification capability
+    pub aircraft_identification: bool,
+    /// Squitter capability subfield (SCS)
+    pub squitter: bool,
+    /// Surveillance identifier code (SI) capability
+    pub surveillance_identifier: bool,
+    /// Common usage GICB capability report, toggles on every change of BDS 1.7
+    pub common_usage_gicb: bool,
+    /// ACAS hybrid surveillance capability
+    pub hybrid_surveillance: bool,
+    /// ACAS generates RAs, `false` when TAs only
+    pub acas_resolution_advisories: bool,
+    /// RTCA DO-185 version of ACAS, 0 DO-185, 1 DO-185A, 2 DO-185B
+    pub acas_version: u32,
+    /// Data terminal equipment (DTE) status, one bit per subaddress
+    pub dte_status: u32,
+}
+
+impl Display for DataLinkCapability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let capabilities = [
+            (self.overlay_command, "OCC"),
+            (self.acas, "ACAS"),
+            (self.enhanced_protocol, "level 5"),
+            (self.specific_services, "specific services"),
+            (self.aircraft_identification, "ident"),
+            (self.squitter, "squitter"),
+            (self.surveillance_identifier, "SI"),
+            (self.hybrid_surveillance, "hybrid"),
+            (self.acas_resolution_advisories, "RA"),
+        ];
+        write!(f, "version {}", self.subnetwork_version)?;
+        for (_, name) in capabilities.iter().filter(|(v, _)| *v) {
+            write!(f, " {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a data link capability report (BDS 1.0).
+///
+/// Reserved bits 10-14 must be clear, and the overlay command capability goes with subnetwork version 5 or later.
+pub(crate) fn bds_1_0(message: &[u32]) -> Option<DataLinkCapability> {
+    if decoder::range_value(message, 33, 40)? != 0x10 || decoder::range_value(message, 42, 46)? != 0
+    {
+        return None;
+    }
+    let overlay_command = decoder::range_value(message, 47, 47)? == 1;
+    let subnetwork_version = decoder::range_value(message, 49, 55)?;
+    if overlay_command != (subnetwork_version >= 5) {
+        return None;
+    }
+    Some(DataLinkCapability {
+        continuation: decoder::range_value(message, 41, 41)? == 1,
+        overlay_command,
+        acas: decoder::range_value(message, 48, 48)? == 1,
+        subnetwork_version,
+        enhanced_protocol: decoder::range_value(message, 56, 56)? == 1,
+        specific_services: decoder::range_value(message, 57, 57)? == 1,
+        uplink_elm: decoder::range_value(message, 58, 60)?,
+        downlink_elm: decoder::range_value(message, 61, 64)?,
+        aircraft_identification: decoder::range_value(message, 65, 65)? == 1,
+        squitter: decoder::range_value(message, 66, 66)? == 1,
+        surveillance_identifier: decoder::range_value(message, 67, 67)? == 1,
+        common_usage_gicb: decoder::range_value(message, 68, 68)? == 1,
+        hybrid_surveillance: decoder::range_value(message, 69, 69)? == 1,
+        acas_resolution_advisories: decoder::range_value(message, 70, 70)? == 1,
+        acas_version: decoder::range_value(message, 71, 72)?,
+        dte_status: decoder::range_value(message, 73, 88)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_1_0() {
+        let message = get_message("A800178D10010080F50000D5893C").expect("Failed to get message");
+        let capability = bds_1_0(&message).expect("Failed to decode BDS 1.0");
+        assert!(!capability.continuation);
+        assert!(!capability.overlay_command);
+        assert!(capability.acas);
+        assert_eq!(capability.subnetwork_version, 0);
+        assert!(capability.specific_services);
+        assert!(capability.aircraft_identification);
+        assert!(capability.squitter);
+        assert!(capability.surveillance_identifier);
+        assert!(capability.common_usage_gicb);
+        assert!(!capability.hybrid_surveillance);
+        assert!(capability.acas_resolution_advisories);
+        assert_eq!(capability.acas_version, 1);
+        assert_eq!(
+            capability.to_string(),
+            "version 0 ACAS specific services ident squitter SI RA"
+        );
+
+        let message = get_message("A828010A10010080F600001C4F59").expect("Failed to get message");
+        let capability = bds_1_0(&message).expect("Failed to decode BDS 1.0");
+        assert!(capability.acas_resolution_advisories);
+        assert_eq!(capability.acas_version, 2);
+
+        // Overlay command capability with subnetwork version 1
+        let message = get_message("A800178D10030380F50000D5893C").expect("Failed to get message");
+        assert_eq!(bds_1_0(&message), None);
+    }
+}
@@ -1,6 +1,35 @@
 use crate::decoder;
+use std::fmt::{self, Display};
 
-#[derive(Debug)]
+/// Registers of the common usage GICB capability report, ME bits 1 to 24.
+const GICB_REGISTERS: [(u32, u32); 24] = [
+    (0, 0x5),
+    (0, 0x6),
+    (0, 0x7),
+    (0, 0x8),
+    (0, 0x9),
+    (0, 0xA),
+    (2, 0x0),
+    (2, 0x1),
+    (4, 0x0),
+    (4, 0x1),
+    (4, 0x2),
+    (4, 0x3),
+    (4, 0x4),
+    (4, 0x5),
+    (4, 0x8),
+    (5, 0x0),
+    (5, 0x1),
+    (5, 0x2),
+    (5, 0x3),
+    (5, 0x4),
+    (5, 0x5),
+    (5, 0x6),
+    (5, 0xF),
+    (6, 0x0),
+];
+
+#[derive(Debug, Clone, Copy)]
 pub struct Capability {
     pub flags: u32,
     pub bds20: bool,
@@ -41,12 +70,46 @@ impl Capability {
     }
 }
 
+impl Capability {
+    /// Returns `true` if the transponder advertises the register.
+    pub fn supports(&self, bds: (u32, u32)) -> bool {
+        GICB_REGISTERS
+            .iter()
+            .position(|&r| r == bds)
+            .is_some_and(|i| (self.flags >> (23 - i)) & 1 == 1)
+    }
+
+    /// Returns the advertised registers.
+    pub fn registers(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        GICB_REGISTERS
+            .iter()
+            .copied()
+            .filter(|&bds| self.supports(bds))
+    }
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.registers()
+                .map(|(b1, b2)| format!("{:X}.{:X}", b1, b2))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
 impl Default for Capability {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Decodes a common usage GICB capability report (BDS 1.7), every register from 0.5 to 6.0.
+///
+/// BDS 2.0 must be advertised and the reserved bits 29-56 clear.
 pub fn is_bds_1_7(message: &[u32]) -> Option<Capability> {
     let (bds20, reserved) = decoder::flag_and_range_value(message, 39, 61, 88)?;
     if bds20 != 1 || reserved != 0 {
@@ -54,12 +117,39 @@ pub fn is_bds_1_7(message: &[u32]) -> Option<Capability> {
     }
 
     let capability = decoder::range_value(message, 33, 56)?;
-    Some(Capability::from_data(
-        capability,
-        true,
-        ((capability >> 15) & 1) == 1,
-        ((capability >> 11) & 1) == 1,
-        ((capability >> 8) & 1) == 1,
-        (capability & 1) == 1,
-    ))
+    let mut result = Capability {
+        flags: capability,
+        ..Capability::default()
+    };
+    result.bds20 = result.supports((2, 0));
+    result.bds40 = result.supports((4, 0));
+    result.bds44 = result.supports((4, 4));
+    result.bds50 = result.supports((5, 0));
+    result.bds60 = result.supports((6, 0));
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_1_7() {
+        let message = get_message("A0001838FA81C10000000081A92F").expect("Failed to get message");
+        let capability = is_bds_1_7(&message).expect("Failed to decode BDS 1.7");
+        assert!(capability.bds20 && capability.bds40 && capability.bds50 && capability.bds60);
+        assert!(!capability.bds44);
+        assert!(!capability.supports((1, 7)));
+        assert_eq!(
+            capability.to_string(),
+            "0.5 0.6 0.7 0.8 0.9 2.0 4.0 5.0 5.1 5.2 6.0"
+        );
+
+        let message = get_message("A0001838FA81C18000000081A92F").expect("Failed to get message");
+        assert!(is_bds_1_7(&message).is_some());
+
+        let message = get_message("A0001838FA81C10000100081A92F").expect("Failed to get message");
+        assert!(is_bds_1_7(&message).is_none());
+    }
 }
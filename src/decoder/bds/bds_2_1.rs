@@ -0,0 +1,91 @@
+use super::characters;
+use crate::decoder;
+use std::fmt::{self, Display};
+
+/// Aircraft and airline registration markings (BDS 2.1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registration {
+    /// Aircraft registration marks, e.g. G-EUPT
+    pub aircraft: Option<String>,
+    /// ICAO airline registration marking, two letters
+    pub airline: Option<String>,
+}
+
+impl Display for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.aircraft
+                .as_deref()
+                .or(self.airline.as_deref())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Decodes aircraft registration markings (BDS 2.1), a status bit followed by 7 characters (bits 1-43),
+/// and the airline registration marking, a status bit followed by 2 characters (bits 44-56).
+///
+/// The register has no BDS code, the marks must be letters, digits and a hyphen, left aligned,
+/// and the fields with a clear status bit must be empty.
+pub(crate) fn is_bds_2_1(message: &[u32]) -> Option<Registration> {
+    let aircraft = if decoder::range_value(message, 33, 33)? == 1 {
+        Some(aircraft_registration(&characters(message, 34, 7)?)?)
+    } else if (0..7).all(|i| decoder::range_value(message, 34 + i * 6, 39 + i * 6) == Some(0)) {
+        None
+    } else {
+        return None;
+    };
+    let airline = if decoder::range_value(message, 76, 76)? == 1 {
+        let marking = characters(message, 77, 2)?;
+        if !marking.chars().all(|c| c.is_ascii_uppercase()) {
+            return None;
+        }
+        Some(marking)
+    } else if decoder::range_value(message, 77, 88)? == 0 {
+        None
+    } else {
+        return None;
+    };
+    if aircraft.is_none() && airline.is_none() {
+        return None;
+    }
+    Some(Registration { aircraft, airline })
+}
+
+fn aircraft_registration(marks: &str) -> Option<String> {
+    let marks = marks.trim_end();
+    if marks.len() < 2
+        || marks.starts_with('-')
+        || marks.ends_with('-')
+        || marks.matches('-').count() > 1
+        || !marks
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-')
+        || !marks.chars().any(|c| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+    Some(marks.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_2_1() {
+        // G-EUPT, airline BA
+        let message = get_message("A00018388F68AAA0A41081E2C35B").expect("Failed to get message");
+        let registration = is_bds_2_1(&message).expect("Failed to decode BDS 2.1");
+        assert_eq!(registration.aircraft.as_deref(), Some("G-EUPT"));
+        assert_eq!(registration.airline.as_deref(), Some("BA"));
+        assert_eq!(registration.to_string(), "G-EUPT");
+
+        // Leading space
+        let message = get_message("A0001838C03DA2AA829081E2C35B").expect("Failed to get message");
+        assert_eq!(is_bds_2_1(&message), None);
+    }
+}
@@ -0,0 +1,108 @@
+use super::characters;
+use crate::decoder;
+use std::fmt::{self, Display};
+
+/// Aircraft type (BDS 2.5).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AircraftType {
+    /// ICAO aircraft type designator, e.g. A319
+    pub designator: String,
+    /// L landplane, S seaplane, A amphibian, H helicopter, G gyrocopter, T tilt-rotor
+    pub description: char,
+    /// Number of engines, `None` for coupled engines
+    pub engines: Option<u32>,
+    /// J jet, T turboprop or turboshaft, P piston, E electric, R rocket
+    pub engine_type: char,
+    /// Wake turbulence category, L light, M medium, H heavy, J super
+    pub wake_category: char,
+}
+
+impl Display for AircraftType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}{}{} {}",
+            self.designator,
+            self.description,
+            self.engines
+                .map(|v| v.to_string())
+                .unwrap_or("C".to_string()),
+            self.engine_type,
+            self.wake_category
+        )
+    }
+}
+
+/// Decodes the aircraft type (BDS 2.5): the type designator, 4 characters (bits 9-32),
+/// the aircraft description (bits 33-35), number of engines (bits 36-39, 15 coupled),
+/// engine type (bits 40-42) and wake turbulence category (bits 43-44).
+///
+/// The designator must start with a letter and hold 2 to 4 letters and digits, bits 45-56 are reserved.
+pub(crate) fn bds_2_5(message: &[u32]) -> Option<AircraftType> {
+    if decoder::range_value(message, 77, 88)? != 0 {
+        return None;
+    }
+    let designator = characters(message, 41, 4)?.trim_end().to_string();
+    if !(2..=4).contains(&designator.len())
+        || !designator.starts_with(|c: char| c.is_ascii_uppercase())
+        || !designator
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(AircraftType {
+        designator,
+        description: match decoder::range_value(message, 65, 67)? {
+            1 => 'L',
+            2 => 'S',
+            3 => 'A',
+            4 => 'H',
+            5 => 'G',
+            6 => 'T',
+            _ => return None,
+        },
+        engines: match decoder::range_value(message, 68, 71)? {
+            15 => None,
+            v @ 1..=8 => Some(v),
+            _ => return None,
+        },
+        engine_type: match decoder::range_value(message, 72, 74)? {
+            1 => 'J',
+            2 => 'T',
+            3 => 'P',
+            4 => 'E',
+            5 => 'R',
+            _ => return None,
+        },
+        wake_category: match decoder::range_value(message, 75, 76)? {
+            0 => 'L',
+            1 => 'M',
+            2 => 'H',
+            _ => 'J',
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_2_5() {
+        let message = get_message("A000183825073C79245000E2C35B").expect("Failed to get message");
+        let aircraft_type = bds_2_5(&message).expect("Failed to decode BDS 2.5");
+        assert_eq!(aircraft_type.designator, "A319");
+        assert_eq!(aircraft_type.engines, Some(2));
+        assert_eq!(aircraft_type.to_string(), "A319 L2J M");
+
+        let message = get_message("A000183825232D60245000E2C35B").expect("Failed to get message");
+        let aircraft_type = bds_2_5(&message).expect("Failed to decode BDS 2.5");
+        assert_eq!(aircraft_type.designator, "H25");
+
+        // Designator starting with a digit
+        let message = get_message("A000183825CC1C79245000E2C35B").expect("Failed to get message");
+        assert_eq!(bds_2_5(&message), None);
+    }
+}
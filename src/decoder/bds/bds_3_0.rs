@@ -0,0 +1,32 @@
+use crate::decoder::{self, ResolutionAdvisory};
+
+/// Decodes the ACAS active resolution advisory register (BDS 3.0) of a Comm-B reply.
+///
+/// The MB field follows the layout of TC 28 subtype 2, from the ARA at bit 41 to the threat identity.
+/// Registers without a current or terminated advisory return `None`.
+pub(crate) fn bds_3_0(message: &[u32]) -> Option<ResolutionAdvisory> {
+    (decoder::bds(message) == (3, 0))
+        .then(|| decoder::resolution_advisory(message))
+        .flatten()
+        .filter(|ra| ra.is_active() || ra.terminated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{Threat, get_message};
+
+    #[test]
+    fn test_bds_3_0() {
+        let message = get_message("A000183830C20005210358E2C35B").expect("Failed to get message");
+        let ra = bds_3_0(&message).expect("Failed to decode BDS 3.0");
+        assert_eq!(ra.threat, Some(Threat::Icao(0x4840D6)));
+        assert_eq!(ra.abbreviation(), "RA\u{2191}");
+
+        let message = get_message("A0001838CA380F0A82000049E94A").expect("Failed to get message");
+        assert_eq!(bds_3_0(&message), None);
+
+        let message = get_message("A8000096300000000000007F5EBC").expect("Failed to get message");
+        assert_eq!(bds_3_0(&message), None);
+    }
+}
@@ -1,6 +1,6 @@
 use log::debug;
 
-use crate::decoder;
+use crate::decoder::{self, AutopilotModes};
 
 #[derive(Debug)]
 pub struct SelectedVerticalIntention {
@@ -8,6 +8,8 @@ pub struct SelectedVerticalIntention {
     pub fms_selected_altitude: Option<u32>,
     pub barometric_pressure_setting: Option<u32>,
     pub target_altitude_source: Option<u32>,
+    /// VNAV, ALT HOLD and APPROACH of the MCP/FCU mode bits
+    pub autopilot_modes: Option<AutopilotModes>,
 }
 
 impl SelectedVerticalIntention {
@@ -17,6 +19,7 @@ impl SelectedVerticalIntention {
             fms_selected_altitude: None,
             barometric_pressure_setting: None,
             target_altitude_source: None,
+            autopilot_modes: None,
         }
     }
 
@@ -25,12 +28,14 @@ impl SelectedVerticalIntention {
         fms_selected_altitude: Option<u32>,
         barometric_pressure_setting: Option<u32>,
         target_altitude_source: Option<u32>,
+        autopilot_modes: Option<AutopilotModes>,
     ) -> Self {
         SelectedVerticalIntention {
             mcp_selected_altitude,
             fms_selected_altitude,
             barometric_pressure_setting,
             target_altitude_source,
+            autopilot_modes,
         }
     }
 }
@@ -41,12 +46,16 @@ impl Default for SelectedVerticalIntention {
     }
 }
 
+/// Decodes a selected vertical intention (BDS 4.0),
+/// the MCP/FCU mode bits must be clear when their status bit is.
 pub fn is_bds_4_0(message: &[u32]) -> Option<SelectedVerticalIntention> {
     if !decoder::goodflags(message, 33, 34, 45)
         || !decoder::goodflags(message, 46, 47, 58)
         || !decoder::goodflags(message, 59, 60, 71)
         || decoder::goodflags(message, 33, 72, 79)
         || decoder::goodflags(message, 33, 84, 85)
+        || decoder::flag_and_range_value(message, 80, 81, 83)
+            .is_some_and(|(status, modes)| status == 0 && modes != 0)
     {
         return None;
     }
@@ -56,6 +65,7 @@ pub fn is_bds_4_0(message: &[u32]) -> Option<SelectedVerticalIntention> {
         decoder::fms_selected_altitude(message).filter(|x| (0..=65530).contains(x)),
         decoder::barometric_pressure_setting(message).filter(|x| (800..=1210).contains(x)),
         decoder::target_altitude_source(message).filter(|x| (0..=3).contains(x)),
+        decoder::mcp_modes(message),
     );
     debug!("BDS:4.0 {:?}", intent);
     if intent.mcp_selected_altitude.is_some() || intent.fms_selected_altitude.is_some() {
@@ -64,3 +74,24 @@ pub fn is_bds_4_0(message: &[u32]) -> Option<SelectedVerticalIntention> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_4_0_modes() {
+        let message = get_message("A80002BDD01E80F0A8014597E7A8").expect("Failed to get message");
+        let intent = is_bds_4_0(&message).expect("Failed to decode BDS 4.0");
+        assert_eq!(intent.mcp_selected_altitude, Some(41008));
+        assert_eq!(
+            intent.autopilot_modes.and_then(|modes| modes.altitude_hold),
+            Some(true)
+        );
+
+        // ALT HOLD without the mode status
+        let message = get_message("A80002BDD01E80F0A8004597E7A8").expect("Failed to get message");
+        assert!(is_bds_4_0(&message).is_none());
+    }
+}
@@ -0,0 +1,36 @@
+use super::characters;
+use crate::decoder;
+
+/// Decodes the next waypoint identifier (BDS 4.1), a status bit followed by 9 characters (bits 1-55).
+///
+/// The identifier must be 2 to 9 letters and digits starting with a letter, bit 56 is reserved.
+pub(crate) fn is_bds_4_1(message: &[u32]) -> Option<String> {
+    if decoder::range_value(message, 33, 33)? != 1 || decoder::range_value(message, 88, 88)? != 0 {
+        return None;
+    }
+    let identifier = characters(message, 34, 9)?.trim_end().to_string();
+    if identifier.len() < 2
+        || !identifier.starts_with(|c: char| c.is_ascii_uppercase())
+        || !identifier
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_4_1() {
+        let message = get_message("A00018389809B041041040E2C35B").expect("Failed to get message");
+        assert_eq!(is_bds_4_1(&message).as_deref(), Some("LAM"));
+
+        let message = get_message("A0001838C06026C1041040E2C35B").expect("Failed to get message");
+        assert_eq!(is_bds_4_1(&message), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::decoder;
+use std::fmt::{self, Display};
+
+/// Next waypoint position (BDS 4.2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaypointPosition {
+    pub lat: f64,
+    pub lon: f64,
+    /// Crossing altitude (ft)
+    pub crossing_altitude: Option<i32>,
+}
+
+impl Display for WaypointPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.5},{:.5},", self.lat, self.lon)?;
+        if let Some(altitude) = self.crossing_altitude {
+            write!(f, "{}", altitude)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the next waypoint position (BDS 4.2): latitude (bits 1-20), longitude (bits 21-40),
+/// each a status, a sign and 18 bits with the MSB 90°, and the crossing altitude (bits 41-56),
+/// a status, a sign and 14 bits with the LSB 8 ft.
+///
+/// The position must be present and in range, and the altitude zero when its status is clear
+/// or between -1000 and 50000 ft.
+pub(crate) fn is_bds_4_2(message: &[u32]) -> Option<WaypointPosition> {
+    let lat = decoder::status_flag_and_range_value(message, 33, 34, 35, 52)?;
+    let lon = decoder::status_flag_and_range_value(message, 53, 54, 55, 72)?;
+    let altitude = decoder::status_flag_and_range_value(message, 73, 74, 75, 88)?;
+    if lat.0 != 1 || lon.0 != 1 || (altitude.0 == 0 && (altitude.1, altitude.2) != (0, 0)) {
+        return None;
+    }
+    let lat = signed(lat.1, lat.2, 18) as f64 * 90.0 / 131072.0;
+    let lon = signed(lon.1, lon.2, 18) as f64 * 90.0 / 131072.0;
+    if !(-90.0..=90.0).contains(&lat) || (lat, lon) == (0.0, 0.0) {
+        return None;
+    }
+    let crossing_altitude = (altitude.0 == 1).then(|| signed(altitude.1, altitude.2, 14) * 8);
+    if crossing_altitude.is_some_and(|v| !(-1000..=50000).contains(&v)) {
+        return None;
+    }
+    Some(WaypointPosition {
+        lat,
+        lon,
+        crossing_altitude,
+    })
+}
+
+fn signed(sign: u32, value: u32, bits: u32) -> i32 {
+    match sign {
+        0 => value as i32,
+        _ => value as i32 - (1 << bits),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_4_2() {
+        let message = get_message("A0001838925CFFFF23836BE2C35B").expect("Failed to get message");
+        let position = is_bds_4_2(&message).expect("Failed to decode BDS 4.2");
+        assert!((position.lat - 51.64611).abs() < 0.001);
+        assert!((position.lon - -0.15167).abs() < 0.001);
+        assert_eq!(position.crossing_altitude, Some(7000));
+
+        let message = get_message("A0001838925CFFFF23036BE2C35B").expect("Failed to get message");
+        assert_eq!(is_bds_4_2(&message), None);
+    }
+}
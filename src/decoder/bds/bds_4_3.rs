@@ -0,0 +1,90 @@
+use crate::decoder;
+use std::fmt::{self, Display};
+
+/// Bearing, time and distance to the next waypoint (BDS 4.3).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaypointBearing {
+    /// Bearing to the waypoint (°)
+    pub bearing: Option<f64>,
+    /// Time to go (min)
+    pub time_to_go: Option<f64>,
+    /// Distance to go (NM)
+    pub distance: Option<f64>,
+}
+
+impl Display for WaypointBearing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{}",
+            self.bearing
+                .map(|v| format!("{:.0}", v))
+                .unwrap_or_default(),
+            self.time_to_go
+                .map(|v| format!("{:.1}", v))
+                .unwrap_or_default(),
+            self.distance
+                .map(|v| format!("{:.1}", v))
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Decodes the bearing (bits 1-12, a status, a sign and 10 bits with the MSB 90°),
+/// time to go (bits 13-25, a status and 12 bits with the LSB 1/8 min) and
+/// distance to go (bits 26-42, a status and 16 bits with the LSB 0.1 NM) to the next waypoint (BDS 4.3).
+///
+/// Fields with a clear status must be zero, bits 43-56 are reserved, the distance is at most 2000 NM
+/// and the speed it implies with the time to go between 60 and 650 kt.
+pub(crate) fn is_bds_4_3(message: &[u32]) -> Option<WaypointBearing> {
+    let bearing = decoder::status_flag_and_range_value(message, 33, 34, 35, 44)?;
+    let time_to_go = decoder::flag_and_range_value(message, 45, 46, 57)?;
+    let distance = decoder::flag_and_range_value(message, 58, 59, 74)?;
+    if decoder::range_value(message, 75, 88)? != 0
+        || (bearing.0 == 0 && (bearing.1, bearing.2) != (0, 0))
+        || (time_to_go.0 == 0 && time_to_go.1 != 0)
+        || (distance.0 == 0 && distance.1 != 0)
+        || bearing.0 + time_to_go.0 + distance.0 == 0
+    {
+        return None;
+    }
+    let time_to_go = (time_to_go.0 == 1).then_some(time_to_go.1 as f64 / 8.0);
+    let distance = (distance.0 == 1).then_some(distance.1 as f64 / 10.0);
+    if distance.is_some_and(|v| v > 2000.0)
+        || time_to_go
+            .zip(distance)
+            .is_some_and(|(time, distance)| !(60.0..=650.0).contains(&(distance / time * 60.0)))
+    {
+        return None;
+    }
+    Some(WaypointBearing {
+        bearing: (bearing.0 == 1).then(|| {
+            let value = match bearing.1 {
+                0 => bearing.2 as f64,
+                _ => bearing.2 as f64 - 1024.0,
+            };
+            (value * 90.0 / 512.0).rem_euclid(360.0)
+        }),
+        time_to_go,
+        distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_bds_4_3() {
+        let message = get_message("A0001838E0083240D54000E2C35B").expect("Failed to get message");
+        let bearing = is_bds_4_3(&message).expect("Failed to decode BDS 4.3");
+        assert_eq!(bearing.bearing, Some(270.0));
+        assert_eq!(bearing.time_to_go, Some(12.5));
+        assert_eq!(bearing.distance, Some(85.3));
+        assert_eq!(bearing.to_string(), "270,12.5,85.3");
+
+        let message = get_message("A000183860083240D54000E2C35B").expect("Failed to get message");
+        assert_eq!(is_bds_4_3(&message), None);
+    }
+}
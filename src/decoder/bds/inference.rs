@@ -0,0 +1,404 @@
+use super::{
+    Capability, HeadingAndSpeed, Meteo, Registration, SelectedVerticalIntention, TrackAndTurn,
+    WaypointBearing, WaypointPosition, is_bds_2_1, is_bds_4_0, is_bds_4_1, is_bds_4_2, is_bds_4_3,
+    is_bds_4_4, is_bds_4_5, is_bds_5_0, is_bds_6_0,
+};
+use crate::decoder::haversine;
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::sync::Mutex;
+
+/// Score of a candidate nothing could be checked against.
+const NEUTRAL_SCORE: f64 = 0.5;
+
+lazy_static! {
+    static ref BDS_STATISTICS: Mutex<BdsStatistics> = Mutex::new(BdsStatistics::default());
+}
+
+/// State of the aircraft known from ADS-B and earlier replies, the Comm-B candidates are checked against it.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct KnownState {
+    pub(crate) altitude: Option<u32>,
+    pub(crate) grspeed: Option<u32>,
+    pub(crate) track: Option<u32>,
+    pub(crate) heading: Option<u32>,
+    pub(crate) vrate: Option<i32>,
+    pub(crate) selected_altitude: Option<u32>,
+    pub(crate) position: Option<(f64, f64)>,
+}
+
+/// A Comm-B register a reply without BDS code decodes to.
+pub(crate) enum CommB {
+    SelectedVerticalIntention(SelectedVerticalIntention),
+    TrackAndTurn(TrackAndTurn),
+    HeadingAndSpeed(HeadingAndSpeed),
+    Meteo(Meteo),
+    Temperature(f64),
+    Registration(Registration),
+    NextWaypoint(String),
+    WaypointPosition(WaypointPosition),
+    WaypointBearing(WaypointBearing),
+}
+
+impl CommB {
+    pub(crate) fn bds(&self) -> (u32, u32) {
+        match self {
+            CommB::SelectedVerticalIntention(_) => (4, 0),
+            CommB::TrackAndTurn(_) => (5, 0),
+            CommB::HeadingAndSpeed(_) => (6, 0),
+            CommB::Meteo(_) => (4, 4),
+            CommB::Temperature(_) => (4, 5),
+            CommB::Registration(_) => (2, 1),
+            CommB::NextWaypoint(_) => (4, 1),
+            CommB::WaypointPosition(_) => (4, 2),
+            CommB::WaypointBearing(_) => (4, 3),
+        }
+    }
+
+    /// Mean agreement, 0 to 1, of the register with the known state.
+    fn score(&self, state: &KnownState) -> f64 {
+        let checks = match self {
+            CommB::SelectedVerticalIntention(v) => vec![
+                v.mcp_selected_altitude
+                    .or(v.fms_selected_altitude)
+                    .map(|a| agreement(a.abs_diff(round_to(a, 100)) as f64, 50.0)),
+                v.mcp_selected_altitude
+                    .or(v.fms_selected_altitude)
+                    .zip(state.selected_altitude)
+                    .map(|(a, b)| agreement(a.abs_diff(b) as f64, 1000.0)),
+            ],
+            CommB::TrackAndTurn(v) => vec![
+                v.ground_speed
+                    .zip(state.grspeed)
+                    .map(|(a, b)| agreement(a.abs_diff(b) as f64, 50.0)),
+                v.track_angle
+                    .zip(state.track)
+                    .map(|(a, b)| agreement(angle_difference(a, b), 20.0)),
+                v.roll_angle
+                    .zip(v.true_airspeed)
+                    .zip(v.track_angle_rate)
+                    .filter(|&((_, tas), _)| tas > 0)
+                    .map(|((roll, tas), rate)| {
+                        let expected = 1091.0 * (roll as f64).to_radians().tan() / tas as f64;
+                        agreement((rate as f64 - expected).abs(), 2.0)
+                    }),
+            ],
+            CommB::HeadingAndSpeed(v) => vec![
+                v.barometric_altitude_rate
+                    .or(v.internal_vertical_velocity)
+                    .zip(state.vrate)
+                    .map(|(a, b)| agreement(a.abs_diff(b) as f64, 1000.0)),
+                v.magnetic_heading
+                    .zip(state.heading.or(state.track))
+                    .map(|(a, b)| agreement(angle_difference(a, b), 45.0)),
+                v.indicated_airspeed
+                    .zip(v.mach_number)
+                    .zip(state.altitude)
+                    .map(|((ias, mach), altitude)| {
+                        agreement((mach - cas_to_mach(ias as f64, altitude as f64)).abs(), 0.1)
+                    }),
+                v.mach_number.zip(state.altitude).zip(state.grspeed).map(
+                    |((mach, altitude), gs)| {
+                        let tas = mach * speed_of_sound(altitude as f64);
+                        agreement((tas - gs as f64).abs(), 150.0)
+                    },
+                ),
+            ],
+            CommB::Meteo(v) => vec![
+                v.temp
+                    .zip(state.altitude)
+                    .map(|(t, a)| agreement((t - isa_temperature(a as f64)).abs(), 30.0)),
+            ],
+            CommB::Temperature(t) => vec![
+                state
+                    .altitude
+                    .map(|a| agreement((t - isa_temperature(a as f64)).abs(), 30.0)),
+            ],
+            CommB::Registration(_) | CommB::NextWaypoint(_) => vec![],
+            CommB::WaypointPosition(v) => vec![
+                v.crossing_altitude
+                    .map(|a| agreement(a.abs_diff(round_to_i32(a, 100)) as f64, 50.0)),
+                state
+                    .position
+                    .map(|(lat, lon)| agreement(haversine(lat, lon, v.lat, v.lon), 1000.0)),
+            ],
+            CommB::WaypointBearing(v) => vec![
+                v.bearing
+                    .zip(state.track)
+                    .map(|(a, b)| agreement(angle_difference(a.round() as u32, b), 120.0)),
+                v.time_to_go
+                    .zip(v.distance)
+                    .zip(state.grspeed)
+                    .filter(|&((time, _), gs)| time > 0.0 && gs > 0)
+                    .map(|((time, distance), gs)| {
+                        agreement((distance / gs as f64 * 60.0 / time - 1.0).abs(), 0.5)
+                    }),
+            ],
+        };
+        let checks: Vec<f64> = checks.into_iter().flatten().collect();
+        match checks.len() {
+            0 => NEUTRAL_SCORE,
+            n => checks.iter().sum::<f64>() / n as f64,
+        }
+    }
+}
+
+/// The register a reply was assigned to.
+pub(crate) struct Inference {
+    pub(crate) register: CommB,
+    /// Agreement of the register with the known state, 0 to 1
+    pub(crate) score: f64,
+    /// Share of the winning score in the scores of all candidates
+    pub(crate) confidence: f64,
+    /// Number of registers the reply passed the validity checks of
+    pub(crate) candidates: usize,
+}
+
+/// Evaluates every register a reply without BDS code may hold and picks the one most consistent with the known state.
+///
+/// Registers other than 4.4 and 4.5 are only candidates when `capability` advertises them, `None` accepts all.
+/// Ties are resolved in the order 4.0, 5.0, 6.0, 4.4, 4.5, 2.1, 4.1, 4.2, 4.3.
+pub(crate) fn infer_bds(
+    message: &[u32],
+    state: &KnownState,
+    capability: Option<&Capability>,
+) -> Option<Inference> {
+    let advertised = |f: fn(&Capability) -> bool| capability.is_none_or(f);
+    let candidates: Vec<CommB> = [
+        advertised(|c| c.bds40)
+            .then(|| is_bds_4_0(message).map(CommB::SelectedVerticalIntention))
+            .flatten(),
+        advertised(|c| c.bds50)
+            .then(|| is_bds_5_0(message).map(CommB::TrackAndTurn))
+            .flatten(),
+        advertised(|c| c.bds60)
+            .then(|| is_bds_6_0(message).map(CommB::HeadingAndSpeed))
+            .flatten(),
+        is_bds_4_4(message).map(CommB::Meteo),
+        is_bds_4_5(message).map(CommB::Temperature),
+        advertised(|c| c.supports((2, 1)))
+            .then(|| is_bds_2_1(message).map(CommB::Registration))
+            .flatten(),
+        advertised(|c| c.supports((4, 1)))
+            .then(|| is_bds_4_1(message).map(CommB::NextWaypoint))
+            .flatten(),
+        advertised(|c| c.supports((4, 2)))
+            .then(|| is_bds_4_2(message).map(CommB::WaypointPosition))
+            .flatten(),
+        advertised(|c| c.supports((4, 3)))
+            .then(|| is_bds_4_3(message).map(CommB::WaypointBearing))
+            .flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let count = candidates.len();
+    let scores: Vec<f64> = candidates.iter().map(|c| c.score(state)).collect();
+    let total: f64 = scores.iter().sum();
+    let (best, score) =
+        scores
+            .iter()
+            .enumerate()
+            .fold(None, |acc: Option<(usize, f64)>, (i, &s)| match acc {
+                Some((_, b)) if b >= s => acc,
+                _ => Some((i, s)),
+            })?;
+    let register = candidates.into_iter().nth(best)?;
+    Some(Inference {
+        register,
+        score,
+        confidence: if total > 0.0 {
+            score / total
+        } else {
+            1.0 / count as f64
+        },
+        candidates: count,
+    })
+}
+
+/// Counts of the registers assigned to replies without BDS code.
+#[derive(Debug, Default, Clone)]
+pub struct BdsStatistics {
+    /// Replies assigned to a register, by BDS code
+    pub registers: BTreeMap<(u32, u32), u32>,
+    /// Replies more than one register was a candidate for
+    pub ambiguous: u32,
+    /// Sum of the confidence of the ambiguous assignments
+    pub ambiguous_confidence: f64,
+}
+
+impl BdsStatistics {
+    pub fn replies(&self) -> u32 {
+        self.registers.values().sum()
+    }
+
+    /// Mean confidence of the ambiguous assignments.
+    pub fn mean_confidence(&self) -> Option<f64> {
+        match self.ambiguous {
+            0 => None,
+            n => Some(self.ambiguous_confidence / n as f64),
+        }
+    }
+}
+
+impl Display for BdsStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Comm-B ")?;
+        for ((b1, b2), count) in &self.registers {
+            write!(f, "{}.{}:{} ", b1, b2, count)?;
+        }
+        write!(f, "ambiguous:{}/{}", self.ambiguous, self.replies())?;
+        if let Some(confidence) = self.mean_confidence() {
+            write!(f, " confidence:{:.2}", confidence)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn record_inference(inference: &Inference) {
+    let mut statistics = BDS_STATISTICS
+        .lock()
+        .expect("Failed to lock BDS statistics");
+    *statistics
+        .registers
+        .entry(inference.register.bds())
+        .or_insert(0) += 1;
+    if inference.candidates > 1 {
+        statistics.ambiguous += 1;
+        statistics.ambiguous_confidence += inference.confidence;
+    }
+}
+
+/// Returns the registers assigned to replies without BDS code so far.
+pub fn bds_statistics() -> BdsStatistics {
+    BDS_STATISTICS
+        .lock()
+        .expect("Failed to lock BDS statistics")
+        .clone()
+}
+
+/// 1 for equal values falling linearly to 0 at `tolerance`.
+fn agreement(difference: f64, tolerance: f64) -> f64 {
+    (1.0 - difference / tolerance).max(0.0)
+}
+
+fn round_to(value: u32, step: u32) -> u32 {
+    (value + step / 2) / step * step
+}
+
+fn round_to_i32(value: i32, step: i32) -> i32 {
+    (value as f64 / step as f64).round() as i32 * step
+}
+
+fn angle_difference(a: u32, b: u32) -> f64 {
+    let difference = a.abs_diff(b) % 360;
+    difference.min(360 - difference) as f64
+}
+
+/// ISA temperature (°C) at a pressure altitude (ft).
+fn isa_temperature(altitude: f64) -> f64 {
+    (15.0 - 1.98 * altitude / 1000.0).max(-56.5)
+}
+
+/// ISA speed of sound (kt) at a pressure altitude (ft).
+fn speed_of_sound(altitude: f64) -> f64 {
+    38.967854 * (isa_temperature(altitude) + 273.15).sqrt()
+}
+
+/// ISA pressure ratio at a pressure altitude (ft).
+fn pressure_ratio(altitude: f64) -> f64 {
+    match altitude {
+        a if a <= 36089.0 => (1.0 - 6.8756e-6 * a).powf(5.2559),
+        a => 0.22336 * (-(a - 36089.0) / 20806.0).exp(),
+    }
+}
+
+/// Converts a calibrated airspeed (kt) to the Mach number at a pressure altitude (ft).
+fn cas_to_mach(cas: f64, altitude: f64) -> f64 {
+    let impact = (1.0 + 0.2 * (cas / 661.47).powi(2)).powf(3.5) - 1.0;
+    (5.0 * ((impact / pressure_ratio(altitude) + 1.0).powf(2.0 / 7.0) - 1.0)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_cas_to_mach() {
+        assert!((cas_to_mach(250.0, 0.0) - 0.378).abs() < 0.001);
+        assert!((cas_to_mach(280.0, 35000.0) - 0.821).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_infer_bds() {
+        // Passes the checks of both 5.0 and 6.0, as 5.0 it banks 30° at 120 kt TAS
+        let message = get_message("A00011B695B9DB25E1E43C266EF3").expect("Failed to get message");
+        let state = KnownState {
+            altitude: Some(27550),
+            ..Default::default()
+        };
+        let inference = infer_bds(&message, &state, None).expect("Failed to infer BDS");
+        assert_eq!(inference.register.bds(), (6, 0));
+        assert_eq!(inference.candidates, 2);
+        assert!(inference.confidence > 0.9);
+
+        let capability = Capability::from_data(0, false, false, false, true, false);
+        let inference =
+            infer_bds(&message, &state, Some(&capability)).expect("Failed to infer BDS");
+        assert_eq!(inference.register.bds(), (5, 0));
+        assert_eq!(inference.candidates, 1);
+    }
+
+    #[test]
+    fn test_infer_bds_disagreement() {
+        // Both 5.0 and 6.0 disagree with every check, the first in the tie order is still picked
+        let message = get_message("A00011B695B9DB25E1E43C266EF3").expect("Failed to get message");
+        let state = KnownState {
+            altitude: Some(0),
+            grspeed: Some(600),
+            vrate: Some(6000),
+            heading: Some(0),
+            track: Some(0),
+            ..Default::default()
+        };
+        let inference = infer_bds(&message, &state, None).expect("Failed to infer BDS");
+        assert_eq!(inference.register.bds(), (5, 0));
+        assert_eq!(inference.score, 0.0);
+        assert_eq!(inference.confidence, 0.5);
+    }
+
+    #[test]
+    fn test_score() {
+        let state = KnownState {
+            altitude: Some(35000),
+            grspeed: Some(460),
+            track: Some(90),
+            vrate: Some(0),
+            ..Default::default()
+        };
+        let track = CommB::TrackAndTurn(TrackAndTurn::from_data(
+            Some(0),
+            Some(91),
+            Some(0),
+            Some(462),
+            Some(470),
+        ));
+        let heading = CommB::HeadingAndSpeed(HeadingAndSpeed::from_data(
+            Some(200),
+            Some(180),
+            Some(0.4),
+            Some(-3000),
+            None,
+        ));
+        assert!(track.score(&state) > 0.9);
+        assert!(heading.score(&state) < 0.2);
+        assert_eq!(track.score(&KnownState::default()), 1.0);
+        assert_eq!(
+            CommB::Temperature(-20.0).score(&KnownState::default()),
+            NEUTRAL_SCORE
+        );
+    }
+}
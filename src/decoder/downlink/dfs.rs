@@ -8,6 +8,7 @@ use std::{
 use log::debug;
 
 use super::{Ext, Mds, Srt, get_downlink_format};
+use crate::decoder::{AddressType, Capability, Frame, KnownState, is_extended_squitter};
 
 #[derive(Debug)]
 pub enum DF {
@@ -38,14 +39,40 @@ impl Display for DF {
 }
 
 impl DF {
-    pub fn log(&self, downlink_error_log_file: &Mutex<File>) -> Result<(), std::io::Error> {
+    pub fn log(
+        &self,
+        downlink_error_log_file: &Mutex<File>,
+        frame: &Frame,
+    ) -> Result<(), std::io::Error> {
         let mut downlink_error_log_file = downlink_error_log_file
             .lock()
             .expect("Cannot open downlink error log file.");
-        write!(downlink_error_log_file, "{}", self)?;
+        write!(downlink_error_log_file, "{},{}", frame.metadata(), self)?;
         debug!("Writing to {:?}", &downlink_error_log_file);
         Ok(())
     }
+
+    /// Decodes a message, inferring the Comm-B register of DF20/21 replies against the known
+    /// state of the aircraft and the registers it advertises, as `Plane::update` does.
+    pub(crate) fn from_message_with_state<'a>(
+        message: &'a [u32],
+        state: &KnownState,
+        capability: Option<&Capability>,
+    ) -> Result<Self, &'a str> {
+        match get_downlink_format(message) {
+            Some(20 | 21) => Ok(DF::MDS(Mds::from_message_with_state(
+                message, state, capability,
+            ))),
+            _ => DF::from_message(message),
+        }
+    }
+
+    pub fn address_type(&self) -> AddressType {
+        match self {
+            DF::EXT(v) => v.address_type,
+            _ => AddressType::Icao,
+        }
+    }
 }
 
 impl Downlink for DF {
@@ -55,6 +82,9 @@ impl Downlink for DF {
                 let dl = match value {
                     0..=16 => DF::SRT(Srt::from_message(message)?),
                     17 => DF::EXT(Ext::from_message(message)?),
+                    18 if is_extended_squitter(message) => DF::EXT(Ext::from_message(message)?),
+                    // Coarse TIS-B (CF3), management (CF4) and reserved (CF7) messages are not decoded
+                    18 => return Err("DF18 control field without extended squitter"),
                     20 | 21 => DF::MDS(Mds::from_message(message)?),
                     _ => DF::SRT(Srt::new()),
                 };
@@ -91,9 +91,34 @@ impl Display for Ext {
             write!(f, ",")?
         }
         if let Some(v) = self.adsb_version {
-            writeln!(f, ",{:X}", v)
+            write!(f, ",{:X}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.squawk {
+            write!(f, ",{:04}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = &self.emergency {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = &self.resolution_advisory {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = &self.target_state {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",,,,,")?
+        }
+        if let Some(v) = &self.operational_status {
+            writeln!(f, ",{}", v)
         } else {
-            writeln!(f, ",")
+            writeln!(f, ",,,,,,,,")
         }
     }
 }
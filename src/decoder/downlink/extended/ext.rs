@@ -1,7 +1,10 @@
+use crate::decoder::{AddressType, Emergency, OperationalStatus, ResolutionAdvisory, TargetState};
+
 #[derive(Debug)]
 pub struct Ext {
     pub df: Option<u32>,
     pub icao: Option<u32>,
+    pub address_type: AddressType,
     pub capability: u32,
     pub message_type: (u32, u32),
     pub ais: Option<String>,
@@ -20,7 +23,13 @@ pub struct Ext {
     pub vrate: Option<i32>,
     pub vrate_source: Option<char>,
     pub surveillance_status: Option<char>,
+    pub nic_supplement_b: bool,
     pub adsb_version: Option<u32>,
+    pub operational_status: Option<OperationalStatus>,
+    pub squawk: Option<u32>,
+    pub emergency: Option<Emergency>,
+    pub resolution_advisory: Option<ResolutionAdvisory>,
+    pub target_state: Option<TargetState>,
 }
 
 impl Default for Ext {
@@ -34,6 +43,7 @@ impl Ext {
         Ext {
             df: None,
             icao: None,
+            address_type: AddressType::Icao,
             capability: 0,
             message_type: (0, 0),
             ais: None,
@@ -52,7 +62,13 @@ impl Ext {
             vrate: None,
             vrate_source: None,
             surveillance_status: None,
+            nic_supplement_b: false,
             adsb_version: None,
+            operational_status: None,
+            squawk: None,
+            emergency: None,
+            resolution_advisory: None,
+            target_state: None,
         }
     }
 }
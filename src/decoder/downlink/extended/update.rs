@@ -19,6 +19,7 @@ impl Ext {
             9..=18 => {
                 self.altitude = decoder::altitude(message, df);
                 self.surveillance_status = Some(decoder::surveillance_status(message));
+                self.nic_supplement_b = decoder::nic_supplement_b(message);
             }
             _ => {}
         }
@@ -49,8 +50,26 @@ impl Ext {
         self.surveillance_status = Some(decoder::surveillance_status(message));
     }
 
+    fn update_mt_28(&mut self, message: &[u32]) {
+        match self.message_type.1 {
+            1 => {
+                self.emergency = decoder::emergency_state(message);
+                self.squawk = decoder::emergency_squawk(message);
+            }
+            2 => {
+                self.resolution_advisory = decoder::resolution_advisory(message);
+            }
+            _ => {}
+        }
+    }
+
+    fn update_mt_29(&mut self, message: &[u32]) {
+        self.target_state = decoder::target_state(message);
+    }
+
     fn update_mt_31(&mut self, message: &[u32]) {
         self.adsb_version = decoder::version(message);
+        self.operational_status = decoder::operational_status(message);
     }
 }
 
@@ -65,6 +84,7 @@ impl decoder::Downlink for Ext {
         if let Some(df) = decoder::get_downlink_format(message) {
             self.df = Some(df);
             self.icao = decoder::get_icao(message, df);
+            self.address_type = decoder::address_type(message, df);
             self.capability = decoder::get_capability(message);
             self.message_type = decoder::get_message_type(message);
             match self.message_type.0 {
@@ -80,6 +100,12 @@ impl decoder::Downlink for Ext {
                 20..=22 => {
                     self.update_mt_20_22(message);
                 }
+                28 => {
+                    self.update_mt_28(message);
+                }
+                29 => {
+                    self.update_mt_29(message);
+                }
                 31 => {
                     self.update_mt_31(message);
                 }
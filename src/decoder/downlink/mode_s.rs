@@ -1,6 +1,9 @@
 use log::debug;
 
-use crate::decoder::{self, Capability};
+use crate::decoder::{
+    self, AircraftType, AutopilotModes, Capability, DataLinkCapability, DownlinkRequest,
+    FlightStatus, KnownState, Registration, ResolutionAdvisory, WaypointBearing, WaypointPosition,
+};
 use std::fmt::{self, Display};
 
 #[derive(Debug)]
@@ -8,12 +11,22 @@ pub struct Mds {
     pub df: Option<u32>,
     pub icao: Option<u32>,
     pub altitude: Option<u32>,
+    pub flight_status: Option<FlightStatus>,
+    pub downlink_request: Option<DownlinkRequest>,
     pub ais: Option<String>,
+    pub registration: Option<Registration>,
+    pub aircraft_type: Option<AircraftType>,
     pub threat_encounter: Option<char>,
+    pub resolution_advisory: Option<ResolutionAdvisory>,
     pub capability: Option<Capability>,
+    pub data_link_capability: Option<DataLinkCapability>,
     pub selected_altitude: Option<u32>,
+    pub next_waypoint: Option<String>,
+    pub waypoint_position: Option<WaypointPosition>,
+    pub waypoint_bearing: Option<WaypointBearing>,
     pub target_altitude_source: Option<char>,
     pub barometric_pressure_setting: Option<u32>,
+    pub autopilot_modes: Option<AutopilotModes>,
     pub roll_angle: Option<i32>,
     pub track: Option<u32>,
     pub track_angle_rate: Option<i32>,
@@ -45,12 +58,22 @@ impl Mds {
             df: None,
             icao: None,
             altitude: None,
+            flight_status: None,
+            downlink_request: None,
             ais: None,
+            registration: None,
+            aircraft_type: None,
             threat_encounter: None,
+            resolution_advisory: None,
             capability: None,
+            data_link_capability: None,
             selected_altitude: None,
+            next_waypoint: None,
+            waypoint_position: None,
+            waypoint_bearing: None,
             target_altitude_source: None,
             barometric_pressure_setting: None,
+            autopilot_modes: None,
             roll_angle: None,
             track: None,
             track_angle_rate: None,
@@ -85,6 +108,21 @@ impl Display for Mds {
             write!(f, ",")?
         }
         if let Some(v) = self.altitude {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.flight_status {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.downlink_request {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",,,,")?
+        }
+        if let Some(v) = self.resolution_advisory {
             writeln!(f, ",{}", v)
         } else {
             writeln!(f, ",")
@@ -92,18 +130,26 @@ impl Display for Mds {
     }
 }
 
-impl decoder::Downlink for Mds {
-    fn from_message(message: &[u32]) -> Result<Self, &str> {
+impl Mds {
+    /// Decodes a reply, inferring the register of a Comm-B reply without BDS code against
+    /// the known state of the aircraft and the registers it advertises.
+    pub(crate) fn from_message_with_state(
+        message: &[u32],
+        state: &KnownState,
+        capability: Option<&Capability>,
+    ) -> Self {
         let mut dl = Mds::new();
-        dl.update(message);
-        Ok(dl)
+        dl.decode(message, state, capability);
+        dl
     }
 
-    fn update(&mut self, message: &[u32]) {
+    fn decode(&mut self, message: &[u32], state: &KnownState, capability: Option<&Capability>) {
         if let Some(df) = decoder::get_downlink_format(message) {
             self.df = Some(df);
             self.icao = decoder::get_icao(message, df);
             self.altitude = decoder::altitude(message, df);
+            self.flight_status = decoder::flight_status(message);
+            self.downlink_request = decoder::downlink_request(message);
         }
 
         let mut bds = decoder::bds(message);
@@ -112,64 +158,105 @@ impl decoder::Downlink for Mds {
         }
         if bds == (3, 0) {
             self.threat_encounter = decoder::threat_encounter(message);
+            self.resolution_advisory = decoder::bds_3_0(message);
+        }
+        if bds == (2, 5) {
+            self.aircraft_type = decoder::bds_2_5(message);
+        }
+        if bds == (1, 0) {
+            self.data_link_capability = decoder::bds_1_0(message);
         }
         if bds == (0, 0) {
             if let Some(result) = decoder::is_bds_1_7(message) {
                 self.capability = Some(result);
                 bds = (1, 7);
-            } else if let Some(value) = decoder::is_bds_4_0(message) {
-                self.selected_altitude =
-                    value.mcp_selected_altitude.or(value.fms_selected_altitude);
-                self.target_altitude_source = match value.target_altitude_source {
-                    Some(v) => match v {
-                        1 => Some('\u{2081}'),
-                        2 => Some('\u{2082}'),
-                        3 => Some('\u{2083}'),
-                        _ => Some(' '),
-                    },
-                    _ => Some(' '),
-                };
-                self.barometric_pressure_setting = value.barometric_pressure_setting;
-                bds = (4, 0);
-            } else if let Some(result) = decoder::is_bds_5_0(message) {
-                self.roll_angle = result.roll_angle;
-                self.track = result.track_angle;
-                self.track_angle_rate = result.track_angle_rate;
-                self.grspeed = result.ground_speed;
-                self.true_airspeed = result.true_airspeed;
-                self.track_source = Some('\u{2085}');
-                bds = (5, 0);
-            } else if let Some(result) = decoder::is_bds_6_0(message) {
-                self.heading = result.magnetic_heading;
-                self.indicated_airspeed = result.indicated_airspeed;
-                self.mach_number = result.mach_number;
-                self.vrate = match result.barometric_altitude_rate.is_some() {
-                    true => {
-                        self.vrate_source = Some('\u{2086}');
-                        result.barometric_altitude_rate
+            } else if let Some(inference) = decoder::infer_bds(
+                message,
+                &KnownState {
+                    altitude: self.altitude.or(state.altitude),
+                    ..*state
+                },
+                capability,
+            ) {
+                decoder::record_inference(&inference);
+                bds = inference.register.bds();
+                match inference.register {
+                    decoder::CommB::SelectedVerticalIntention(value) => {
+                        self.selected_altitude =
+                            value.mcp_selected_altitude.or(value.fms_selected_altitude);
+                        self.target_altitude_source = match value.target_altitude_source {
+                            Some(v) => match v {
+                                1 => Some('\u{2081}'),
+                                2 => Some('\u{2082}'),
+                                3 => Some('\u{2083}'),
+                                _ => Some(' '),
+                            },
+                            _ => Some(' '),
+                        };
+                        self.barometric_pressure_setting = value.barometric_pressure_setting;
+                        self.autopilot_modes = value.autopilot_modes;
                     }
-                    _ => {
-                        self.vrate_source = Some('\u{2071}');
-                        result.internal_vertical_velocity
+                    decoder::CommB::TrackAndTurn(result) => {
+                        self.roll_angle = result.roll_angle;
+                        self.track = result.track_angle;
+                        self.track_angle_rate = result.track_angle_rate;
+                        self.grspeed = result.ground_speed;
+                        self.true_airspeed = result.true_airspeed;
+                        self.track_source = Some('\u{2085}');
+                    }
+                    decoder::CommB::HeadingAndSpeed(result) => {
+                        self.heading = result.magnetic_heading;
+                        self.indicated_airspeed = result.indicated_airspeed;
+                        self.mach_number = result.mach_number;
+                        self.vrate = match result.barometric_altitude_rate.is_some() {
+                            true => {
+                                self.vrate_source = Some('\u{2086}');
+                                result.barometric_altitude_rate
+                            }
+                            _ => {
+                                self.vrate_source = Some('\u{2071}');
+                                result.internal_vertical_velocity
+                            }
+                        };
+                        self.heading_source = Some('\u{2086}');
+                    }
+                    decoder::CommB::Meteo(meteo) => {
+                        self.temperature = meteo.temp;
+                        if meteo.wind.is_some() {
+                            self.wind = meteo.wind;
+                        }
+                        self.humidity = meteo.humidity;
+                        self.turbulence = meteo.turbulence;
+                        self.pressure = meteo.pressure;
+                    }
+                    decoder::CommB::Temperature(value) => self.temperature = Some(value),
+                    decoder::CommB::Registration(registration) => {
+                        self.registration = Some(registration)
+                    }
+                    decoder::CommB::NextWaypoint(waypoint) => self.next_waypoint = Some(waypoint),
+                    decoder::CommB::WaypointPosition(position) => {
+                        self.waypoint_position = Some(position)
+                    }
+                    decoder::CommB::WaypointBearing(bearing) => {
+                        self.waypoint_bearing = Some(bearing)
                     }
-                };
-                self.heading_source = Some('\u{2086}');
-                bds = (6, 0);
-            } else if let Some(meteo) = decoder::is_bds_4_4(message) {
-                self.temperature = meteo.temp;
-                if meteo.wind.is_some() {
-                    self.wind = meteo.wind;
                 }
-                self.humidity = meteo.humidity;
-                self.turbulence = meteo.turbulence;
-                self.pressure = meteo.pressure;
-                bds = (4, 4);
-            } else {
-                self.temperature = decoder::is_bds_4_5(message);
             }
         }
         debug!("DF:{} BDS:{}.{}", self.df.unwrap_or(0), bds.0, bds.1);
     }
+}
+
+impl decoder::Downlink for Mds {
+    fn from_message(message: &[u32]) -> Result<Self, &str> {
+        let mut dl = Mds::new();
+        dl.update(message);
+        Ok(dl)
+    }
+
+    fn update(&mut self, message: &[u32]) {
+        self.decode(message, &KnownState::default(), None);
+    }
 
     fn icao(&self) -> Option<u32> {
         self.icao
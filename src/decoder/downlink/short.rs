@@ -1,4 +1,4 @@
-use crate::decoder;
+use crate::decoder::{self, AirAirSurveillance, DownlinkRequest, FlightStatus, ResolutionAdvisory};
 use std::fmt::{self, Display};
 
 #[derive(Debug)]
@@ -8,6 +8,10 @@ pub struct Srt {
     pub squawk: Option<u32>,
     pub capability: Option<u32>,
     pub altitude: Option<u32>,
+    pub flight_status: Option<FlightStatus>,
+    pub downlink_request: Option<DownlinkRequest>,
+    pub air_air: Option<AirAirSurveillance>,
+    pub resolution_advisory: Option<ResolutionAdvisory>,
 }
 
 impl Default for Srt {
@@ -24,6 +28,10 @@ impl Srt {
             squawk: None,
             capability: None,
             altitude: None,
+            flight_status: None,
+            downlink_request: None,
+            air_air: None,
+            resolution_advisory: None,
         }
     }
 }
@@ -41,7 +49,7 @@ impl Display for Srt {
             write!(f, ",")?
         }
         if let Some(v) = self.squawk {
-            writeln!(f, ",{}", v)?
+            write!(f, ",{}", v)?
         } else {
             write!(f, ",")?
         }
@@ -51,6 +59,26 @@ impl Display for Srt {
             write!(f, ",")?
         }
         if let Some(v) = self.altitude {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.flight_status {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",")?
+        }
+        if let Some(v) = self.downlink_request {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",,,,")?
+        }
+        if let Some(v) = self.air_air {
+            write!(f, ",{}", v)?
+        } else {
+            write!(f, ",,,,")?
+        }
+        if let Some(v) = self.resolution_advisory {
             writeln!(f, ",{}", v)
         } else {
             writeln!(f, ",")
@@ -69,7 +97,18 @@ impl decoder::Downlink for Srt {
         if let Some(df) = decoder::get_downlink_format(message) {
             self.df = Some(df);
             self.icao = decoder::get_icao(message, df);
+            if df == 4 || df == 5 {
+                self.flight_status = decoder::flight_status(message);
+                self.downlink_request = decoder::downlink_request(message);
+            }
             match df {
+                0 | 16 => {
+                    self.altitude = decoder::altitude(message, df);
+                    self.air_air = decoder::air_air_surveillance(message, df);
+                    if df == 16 {
+                        self.resolution_advisory = decoder::mv_resolution_advisory(message);
+                    }
+                }
                 4 => {
                     self.altitude = decoder::altitude(message, df);
                 }
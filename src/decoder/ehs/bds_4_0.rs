@@ -1,6 +1,6 @@
 use log::debug;
 
-use crate::decoder::{flag_and_range_value, get_hex_message};
+use crate::decoder::{AutopilotModes, flag_and_range_value, get_hex_message};
 
 pub(crate) fn mcp_selected_altitude(message: &[u32]) -> Option<u32> {
     debug!("MCP, {}", get_hex_message(message));
@@ -33,6 +33,19 @@ pub(crate) fn target_altitude_source(message: &[u32]) -> Option<u32> {
         .map(|v| v.1)
 }
 
+/// Decodes the MCP/FCU mode bits VNAV (bit 49), ALT HOLD (bit 50) and APPROACH (bit 51),
+/// `None` when their status bit (48) is clear.
+pub(crate) fn mcp_modes(message: &[u32]) -> Option<AutopilotModes> {
+    flag_and_range_value(message, 80, 81, 83)
+        .filter(|&f| f.0 == 1)
+        .map(|(_, modes)| AutopilotModes {
+            vnav: Some(modes & 0b100 != 0),
+            altitude_hold: Some(modes & 0b010 != 0),
+            approach: Some(modes & 0b001 != 0),
+            ..AutopilotModes::default()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,6 +64,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mcp_modes() {
+        let message = get_message("A80002BDD01E80F0A8014597E7A8").expect("Failed to get message");
+        let modes = mcp_modes(&message).expect("Failed to get MCP modes");
+        assert_eq!(
+            (modes.vnav, modes.altitude_hold, modes.approach),
+            (Some(false), Some(true), Some(false))
+        );
+        assert_eq!(modes.to_string(), "  H   ");
+
+        let message = get_message("A8000D89ACF567B0A801064B671D").expect("Failed to get message");
+        let modes = mcp_modes(&message).expect("Failed to get MCP modes");
+        assert_eq!(modes.to_string(), "      ");
+
+        let message = get_message("A80004BAACF6427180000078379E").expect("Failed to get message");
+        assert_eq!(mcp_modes(&message), None);
+    }
+
     #[test]
     fn test_fsm_selected_altitude_value() {
         let s = [
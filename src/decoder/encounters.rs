@@ -0,0 +1,147 @@
+use super::{Plane, ResolutionAdvisory, Threat};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+const NM: f64 = 1.852;
+/// Altitude (ft) and range (NM) tolerance matching a threat reported by altitude, range and bearing
+const THREAT_ALTITUDE_TOLERANCE: i32 = 200;
+const THREAT_RANGE_TOLERANCE: f64 = 0.3;
+
+/// ACAS resolution advisory of an aircraft, linked to the aircraft it was issued against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encounter {
+    pub timestamp: DateTime<Utc>,
+    /// Address of the aircraft reporting the advisory
+    pub icao: u32,
+    /// Key of the threat aircraft, reported by address or found by altitude and range
+    pub threat: Option<u32>,
+    pub resolution_advisory: ResolutionAdvisory,
+    /// Horizontal (NM) and vertical (ft) separation of both aircraft when known
+    pub range: Option<f64>,
+    pub vertical_separation: Option<i32>,
+}
+
+impl Encounter {
+    /// Records the current resolution advisory of the aircraft `key`.
+    pub(crate) fn new(
+        aircrafts: &HashMap<u32, Plane>,
+        key: u32,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let plane = aircrafts.get(&key)?;
+        let resolution_advisory = plane.resolution_advisory?;
+        let threat = match resolution_advisory.threat {
+            Some(Threat::Icao(icao)) => aircrafts.contains_key(&icao).then_some(icao),
+            Some(Threat::Position {
+                altitude, range, ..
+            }) => find_threat(aircrafts, key, altitude, range),
+            None => None,
+        };
+        let threat_plane = threat.and_then(|threat| aircrafts.get(&threat));
+        Some(Encounter {
+            timestamp: now,
+            icao: plane.icao,
+            threat: match resolution_advisory.threat {
+                Some(Threat::Icao(icao)) => Some(icao),
+                _ => threat,
+            },
+            resolution_advisory,
+            range: threat_plane
+                .and_then(|threat_plane| plane.distance_to(threat_plane))
+                .map(|distance| distance / NM),
+            vertical_separation: threat_plane
+                .and_then(|threat_plane| threat_plane.altitude.zip(plane.altitude))
+                .map(|(threat_altitude, altitude)| threat_altitude as i32 - altitude as i32),
+        })
+    }
+}
+
+impl Display for Encounter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:06X} ACAS RA against ", self.icao)?;
+        match self.threat {
+            Some(threat) => write!(f, "{:06X}", threat)?,
+            None => write!(f, "unknown")?,
+        }
+        if let Some(range) = self.range {
+            write!(f, " at {:.1}NM", range)?;
+        }
+        if let Some(vertical_separation) = self.vertical_separation {
+            write!(f, " {:+}ft", vertical_separation)?;
+        }
+        write!(f, ": {}", self.resolution_advisory)
+    }
+}
+
+/// Finds the aircraft closest to the reported altitude and range of a threat without a Mode S address.
+fn find_threat(
+    aircrafts: &HashMap<u32, Plane>,
+    key: u32,
+    altitude: Option<i32>,
+    range: Option<f64>,
+) -> Option<u32> {
+    let plane = aircrafts.get(&key)?;
+    let range = range?;
+    aircrafts
+        .iter()
+        .filter(|(other_key, _)| **other_key != key)
+        .filter_map(|(other_key, other)| {
+            let range_error = (plane.distance_to(other)? / NM - range).abs();
+            let altitude_error = match (altitude, other.altitude) {
+                (Some(altitude), Some(other_altitude)) => (other_altitude as i32 - altitude).abs(),
+                (Some(_), None) => return None,
+                (None, _) => 0,
+            };
+            (range_error <= THREAT_RANGE_TOLERANCE && altitude_error <= THREAT_ALTITUDE_TOLERANCE)
+                .then_some((*other_key, range_error))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(other_key, _)| other_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{DF, Downlink, UpdateFromDownlink, get_message};
+
+    fn plane(icao: u32, altitude: u32, position: (f64, f64), now: DateTime<Utc>) -> Plane {
+        let mut plane = Plane::new();
+        plane.icao = icao;
+        plane.altitude = Some(altitude);
+        (plane.lat, plane.lon) = position;
+        plane.position_timestamp = Some(now);
+        plane
+    }
+
+    #[test]
+    fn test_encounter() {
+        let now = Utc::now();
+        let mut aircrafts = HashMap::new();
+        aircrafts.insert(0x4840D6, plane(0x4840D6, 35500, (52.0, 4.0), now));
+        aircrafts.insert(0x400000, plane(0x400000, 10000, (52.0, 4.0741), now));
+
+        let message = get_message("8D40621DE2C20005210358D28CB5").expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        let mut own = plane(0x40621D, 35000, (52.0, 4.02), now);
+        own.update_from_downlink(&downlink);
+        aircrafts.insert(0x40621D, own);
+
+        let encounter = Encounter::new(&aircrafts, 0x40621D, now).expect("Failed to record RA");
+        assert_eq!(encounter.threat, Some(0x4840D6));
+        assert_eq!(encounter.vertical_separation, Some(500));
+        assert_eq!(
+            encounter.to_string(),
+            "40621D ACAS RA against 4840D6 at 0.7NM +500ft: climb; threat 4840D6"
+        );
+
+        // Threat at 10000 ft, 2.0 NM
+        let message = get_message("8D40621DE2E20008D70550A28CCE").expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        if let Some(own) = aircrafts.get_mut(&0x40621D) {
+            own.update_from_downlink(&downlink);
+        }
+        let encounter = Encounter::new(&aircrafts, 0x40621D, now).expect("Failed to record RA");
+        assert_eq!(encounter.threat, Some(0x400000));
+    }
+}
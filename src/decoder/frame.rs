@@ -0,0 +1,84 @@
+use super::checked_message;
+use chrono::{DateTime, Utc};
+
+/// A received squitter together with its reception metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub message: Vec<u32>,
+    /// 48-bit receiver counter (12 MHz ticks) from MLAT capable inputs
+    pub timestamp: Option<u64>,
+    pub signal: Option<u8>,
+    /// Receiver id announced by the feed
+    pub source: Option<u64>,
+    /// Reception time recorded alongside the squitter or given by the reader clock, `None` when unknown
+    pub received: Option<DateTime<Utc>>,
+    /// Number of bits repaired by the error correction
+    pub repaired_bits: u32,
+}
+
+impl Frame {
+    pub fn new(message: Vec<u32>) -> Self {
+        Frame {
+            message,
+            timestamp: None,
+            signal: None,
+            source: None,
+            received: None,
+            repaired_bits: 0,
+        }
+    }
+
+    pub fn from_data(
+        message: Vec<u32>,
+        timestamp: Option<u64>,
+        signal: Option<u8>,
+        source: Option<u64>,
+    ) -> Self {
+        Frame {
+            message,
+            timestamp,
+            signal,
+            source,
+            received: None,
+            repaired_bits: 0,
+        }
+    }
+
+    /// Returns the frame if its message has a valid length and parity, repairing it when enabled.
+    pub(crate) fn validated(mut self) -> Option<Self> {
+        (self.message, self.repaired_bits) = checked_message(self.message)?;
+        Some(self)
+    }
+
+    /// Formats the reception metadata as `timestamp,signal,source` CSV fields.
+    pub(crate) fn metadata(&self) -> String {
+        format!(
+            "{},{},{}",
+            self.timestamp
+                .map(|v| format!("{:012X}", v))
+                .unwrap_or_default(),
+            self.signal.map(|v| v.to_string()).unwrap_or_default(),
+            self.source.map(|v| format!("{:X}", v)).unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+
+    #[test]
+    fn test_metadata() {
+        let message = get_message("02E197B00179C3").expect("Failed to get message");
+        let frame = Frame::from_data(message.clone(), Some(0x05FBF77B10E5), Some(160), None);
+        assert_eq!(frame.metadata(), "05FBF77B10E5,160,");
+        assert_eq!(Frame::new(message).metadata(), ",,");
+    }
+
+    #[test]
+    fn test_validated() {
+        let frame = Frame::from_data(vec![0, 2, 14], Some(1), None, None);
+        assert_eq!(frame.validated(), None);
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use std::{collections::HashMap, sync::Mutex};
+
+/// Addresses recently seen in frames with a parity/interrogator field.
+///
+/// The address of DF0/4/5/16/20/21 replies is only recoverable from the
+/// parity, so any corrupted reply yields some address. Such a reply is
+/// accepted when its address has been seen in a DF11/17/18 frame within the
+/// time to live. The filter time is the latest time an address was seen at.
+pub(crate) struct IcaoFilter {
+    ttl: Duration,
+    seen: HashMap<u32, DateTime<Utc>>,
+    now: Option<DateTime<Utc>>,
+    purged: Option<DateTime<Utc>>,
+}
+
+impl IcaoFilter {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        IcaoFilter {
+            ttl,
+            seen: HashMap::new(),
+            now: None,
+            purged: None,
+        }
+    }
+
+    pub(crate) fn add(&mut self, icao: u32, now: DateTime<Utc>) {
+        self.seen.insert(icao, now);
+        if self.now.is_none_or(|time| now > time) {
+            self.now = Some(now);
+        }
+
+        if self.purged.is_none_or(|purged| now - purged > self.ttl) {
+            let ttl = self.ttl;
+            self.seen.retain(|_, seen| now - *seen <= ttl);
+            self.purged = Some(now);
+        }
+    }
+
+    pub(crate) fn contains(&self, icao: u32) -> bool {
+        match (self.seen.get(&icao), self.now) {
+            (Some(&seen), Some(now)) => now - seen <= self.ttl,
+            _ => false,
+        }
+    }
+}
+
+lazy_static! {
+    static ref ICAO_FILTER: Mutex<Option<IcaoFilter>> = Mutex::new(None);
+}
+
+/// Accepts address/parity replies only from addresses seen in DF11/17/18 within `ttl` seconds.
+///
+/// A `ttl` of 0 disables the filter.
+pub fn set_icao_filter(ttl: i64) {
+    *ICAO_FILTER.lock().expect("Cannot set ICAO filter.") =
+        (ttl > 0).then(|| IcaoFilter::new(Duration::seconds(ttl)));
+}
+
+/// Records an address received in a DF11/17/18 frame.
+pub(crate) fn remember_icao(icao: u32, now: DateTime<Utc>) {
+    if let Some(filter) = ICAO_FILTER
+        .lock()
+        .expect("Failed to lock ICAO filter")
+        .as_mut()
+    {
+        filter.add(icao, now);
+    }
+}
+
+/// Returns `true` if the address recovered from an address/parity reply is acceptable.
+pub(crate) fn is_known_icao(icao: u32) -> bool {
+    ICAO_FILTER
+        .lock()
+        .expect("Failed to lock ICAO filter")
+        .as_ref()
+        .is_none_or(|filter| filter.contains(icao))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icao_filter() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let mut filter = IcaoFilter::new(Duration::seconds(60));
+        assert!(!filter.contains(0x4840D6));
+
+        filter.add(0x4840D6, start);
+        assert!(filter.contains(0x4840D6));
+        assert!(!filter.contains(0x40621D));
+
+        filter.add(0x40621D, start + Duration::seconds(60));
+        assert!(filter.contains(0x4840D6));
+
+        filter.add(0x40621D, start + Duration::seconds(61));
+        assert!(!filter.contains(0x4840D6));
+        assert!(filter.contains(0x40621D));
+        assert_eq!(filter.seen.len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+mod alerts;
 mod from_downlink;
 mod from_squitter;
 mod header;
@@ -9,27 +10,57 @@ pub use from_downlink::UpdateFromDownlink;
 pub use header::{DisplayFlags, LegendHeaders};
 pub use legend::Legend;
 pub use simple_display::format_simple_display;
+pub(crate) use update_position::haversine;
+pub use update_position::set_position_margin;
 
-use super::DF;
-use crate::decoder::Capability;
+use super::{
+    AddressType, AirAirSurveillance, AutopilotModes, DF, Emergency, FlightStatus, Frame,
+    NicSupplements, OperationalStatus, PositionIntegrity, ResolutionAdvisory, TargetState,
+    address_type, position_integrity,
+};
+use crate::decoder::{
+    AircraftType, Capability, DataLinkCapability, KnownState, Registration, WaypointBearing,
+    WaypointPosition,
+};
 use chrono::{DateTime, Utc};
 use std::fmt::{self, Display};
 
+/// Seconds the next waypoint registers are kept after they were last received
+const WAYPOINT_TTL: i64 = 60;
+
 pub struct Plane {
     pub icao: u32,
-    pub capability: (u32, Capability),
+    pub address_type: AddressType,
+    pub capability: (u32, Option<Capability>),
+    pub data_link_capability: Option<DataLinkCapability>,
     pub category: (u32, u32),
     pub reg: &'static str,
+    pub registration: Option<Registration>,
+    pub aircraft_type: Option<AircraftType>,
     pub ais: Option<String>,
     pub altitude: Option<u32>,
     pub altitude_gnss: Option<u32>,
     pub altitude_source: char,
     pub selected_altitude: Option<u32>,
+    pub next_waypoint: Option<String>,
+    pub waypoint_position: Option<WaypointPosition>,
+    pub waypoint_bearing: Option<WaypointBearing>,
+    /// Reception times of BDS 4.1, 4.2 and 4.3
+    pub waypoint_timestamps: [Option<DateTime<Utc>>; 3],
     pub barometric_pressure_setting: Option<u32>,
     pub target_altitude_source: char,
+    pub selected_heading: Option<u32>,
+    pub autopilot_modes: Option<AutopilotModes>,
     pub squawk: Option<u32>,
     pub surveillance_status: char,
     pub threat_encounter: Option<char>,
+    pub emergency: Option<Emergency>,
+    pub resolution_advisory: Option<ResolutionAdvisory>,
+    pub resolution_advisory_timestamp: Option<DateTime<Utc>>,
+    pub flight_status: Option<FlightStatus>,
+    pub flight_status_timestamp: Option<DateTime<Utc>>,
+    pub on_ground: Option<bool>,
+    pub air_air: Option<AirAirSurveillance>,
     pub vrate: Option<i32>,
     pub vrate_source: char,
     pub cpr_lat: [u32; 2],
@@ -57,31 +88,61 @@ pub struct Plane {
     pub humidity: Option<u32>,
     pub pressure: Option<u32>,
     pub timestamp: DateTime<Utc>,
+    /// The timestamp is the reception time of the last frame, not the time it was decoded at
+    pub timestamp_known: bool,
     pub position_timestamp: Option<DateTime<Utc>>,
+    pub position_integrity: Option<PositionIntegrity>,
+    /// The position has been decoded globally or relative to a globally decoded one
+    pub position_confirmed: bool,
+    /// Positions rejected as unreachable from the last confirmed one
+    pub position_rejections: u32,
+    pub position_rejected_timestamp: Option<DateTime<Utc>>,
     pub track_timestamp: Option<DateTime<Utc>>,
     pub heading_timestamp: Option<DateTime<Utc>>,
     pub last_type_code: u32,
     pub last_df: u32,
     pub adsb_version: Option<u32>,
+    pub operational_status: Option<OperationalStatus>,
+    pub frame_timestamp: Option<u64>,
+    pub signal: Option<u8>,
+    pub source: Option<u64>,
+    pub messages: u32,
 }
 
 impl Plane {
     pub fn new() -> Self {
         Plane {
             icao: 0,
-            capability: (0, Capability::default()),
+            address_type: AddressType::Icao,
+            capability: (0, None),
+            data_link_capability: None,
             category: (0, 0),
             reg: "",
+            registration: None,
+            aircraft_type: None,
             ais: None,
             altitude: None,
             altitude_gnss: None,
             altitude_source: ' ',
             selected_altitude: None,
+            next_waypoint: None,
+            waypoint_position: None,
+            waypoint_bearing: None,
+            waypoint_timestamps: [None; 3],
             barometric_pressure_setting: None,
             target_altitude_source: ' ',
+            selected_heading: None,
+            autopilot_modes: None,
             squawk: None,
             surveillance_status: ' ',
             threat_encounter: None,
+            emergency: None,
+            resolution_advisory: None,
+            resolution_advisory_timestamp: None,
+            flight_status: None,
+            flight_status_timestamp: None,
+            on_ground: None,
+            air_air: None,
             vrate: None,
             vrate_source: '_',
             cpr_lat: [0, 0],
@@ -109,30 +170,191 @@ impl Plane {
             humidity: None,
             pressure: None,
             timestamp: Utc::now(),
+            timestamp_known: false,
             position_timestamp: None,
+            position_integrity: None,
+            position_confirmed: false,
+            position_rejections: 0,
+            position_rejected_timestamp: None,
             track_timestamp: None,
             heading_timestamp: None,
             last_type_code: 0,
             last_df: 0,
             adsb_version: None,
+            operational_status: None,
+            frame_timestamp: None,
+            signal: None,
+            source: None,
+            messages: 0,
         }
     }
 
-    pub fn from_message(message: &[u32], df: u32, icao: u32, relaxed: bool) -> Self {
+    pub fn from_message(
+        message: &[u32],
+        df: u32,
+        icao: u32,
+        now: DateTime<Utc>,
+        relaxed: bool,
+    ) -> Self {
         let mut plane = Plane::new();
         plane.icao = icao;
-        (_, plane.reg) = super::icao_to_country(icao);
-        plane.update(message, df, relaxed);
+        plane.address_type = address_type(message, df);
+        plane.cpr_time = [now, now];
+        if plane.address_type.is_icao() {
+            (_, plane.reg) = super::icao_to_country(icao);
+        }
+        plane.update(message, df, now, relaxed);
         plane
     }
 
-    pub fn from_downlink(dl: &DF, icao: u32) -> Self {
+    pub fn from_downlink(dl: &DF, icao: u32, now: DateTime<Utc>) -> Self {
         let mut plane = Plane::new();
         plane.icao = icao;
-        (_, plane.reg) = super::icao_to_country(icao);
+        plane.address_type = dl.address_type();
+        plane.timestamp = now;
+        plane.cpr_time = [now, now];
+        if plane.address_type.is_icao() {
+            (_, plane.reg) = super::icao_to_country(icao);
+        }
         plane.update_from_downlink(dl);
         plane
     }
+
+    /// Stamps the plane with the reception time and metadata of the last frame received from it.
+    pub fn update_reception(&mut self, frame: &Frame, now: DateTime<Utc>) {
+        self.timestamp = now;
+        self.timestamp_known = frame.received.is_some();
+        self.frame_timestamp = frame.timestamp;
+        self.signal = frame.signal;
+        self.source = frame.source;
+        self.messages += 1;
+    }
+
+    /// Derives the integrity of a position message from its type code and the operational status.
+    fn update_position_integrity(&mut self, type_code: u32, nic_supplement_b: bool) {
+        let status = self.operational_status.unwrap_or_default();
+        let supplements = NicSupplements {
+            a: status.nic_supplement_a.unwrap_or(false),
+            b: nic_supplement_b,
+            c: status.nic_supplement_c.unwrap_or(false),
+        };
+        self.position_integrity = position_integrity(
+            type_code,
+            self.adsb_version,
+            supplements,
+            status.nac_p,
+            status.sil,
+        );
+    }
+
+    /// Returns `true` if the integrity of the last position meets `min_nic`, always for 0.
+    pub fn has_position_integrity(&self, min_nic: u32) -> bool {
+        min_nic == 0
+            || self
+                .position_integrity
+                .is_some_and(|integrity| integrity.nic >= min_nic)
+    }
+
+    /// Stores the vertical status and ACAS state of an air-air surveillance reply,
+    /// the TCAS mode is on while ACAS issues resolution advisories.
+    fn update_air_air(&mut self, air_air: Option<AirAirSurveillance>) {
+        let Some(air_air) = air_air else {
+            return;
+        };
+        self.on_ground = Some(air_air.on_ground);
+        self.autopilot_modes
+            .get_or_insert_with(AutopilotModes::default)
+            .tcas = Some(air_air.resolution_advisories());
+        self.air_air = Some(air_air);
+    }
+
+    /// State the Comm-B register of a reply is inferred against, with the registers
+    /// advertised in BDS 1.7 unless `relaxed`.
+    pub(crate) fn comm_b_context(&self, relaxed: bool) -> (KnownState, Option<Capability>) {
+        let state = KnownState {
+            altitude: self.altitude,
+            grspeed: self.grspeed,
+            track: self.track,
+            heading: self.heading,
+            vrate: self.vrate,
+            selected_altitude: self.selected_altitude,
+            position: self.position_timestamp.map(|_| (self.lat, self.lon)),
+        };
+        (state, self.capability.1.filter(|_| !relaxed))
+    }
+
+    /// Next waypoint, its position and bearing, each `None` once not received for `WAYPOINT_TTL` seconds.
+    pub fn route_intent(
+        &self,
+        now: DateTime<Utc>,
+    ) -> (
+        Option<&str>,
+        Option<WaypointPosition>,
+        Option<WaypointBearing>,
+    ) {
+        let fresh = |i: usize| {
+            self.waypoint_timestamps[i].is_some_and(|timestamp| {
+                now.signed_duration_since(timestamp).num_seconds() < WAYPOINT_TTL
+            })
+        };
+        (
+            self.next_waypoint.as_deref().filter(|_| fresh(0)),
+            self.waypoint_position.filter(|_| fresh(1)),
+            self.waypoint_bearing.filter(|_| fresh(2)),
+        )
+    }
+
+    /// Stores the VNAV, altitude hold and approach modes of a selected vertical intention,
+    /// the other modes are only known from ADS-B and ACAS and are kept.
+    fn update_mcp_modes(&mut self, modes: Option<AutopilotModes>) {
+        let Some(modes) = modes else {
+            return;
+        };
+        let autopilot_modes = self
+            .autopilot_modes
+            .get_or_insert_with(AutopilotModes::default);
+        autopilot_modes.vnav = modes.vnav;
+        autopilot_modes.altitude_hold = modes.altitude_hold;
+        autopilot_modes.approach = modes.approach;
+    }
+
+    /// Stores a data link capability report, a toggled GICB capability bit means
+    /// the BDS 1.7 report has changed and the one held is dropped.
+    fn update_data_link_capability(&mut self, capability: Option<DataLinkCapability>) {
+        let Some(capability) = capability else {
+            return;
+        };
+        if self
+            .data_link_capability
+            .is_some_and(|c| c.common_usage_gicb != capability.common_usage_gicb)
+        {
+            self.capability.1 = None;
+        }
+        self.data_link_capability = Some(capability);
+    }
+
+    /// Stores the selected altitude, heading and autopilot modes of a target state and status message.
+    fn update_target_state(&mut self, target_state: &TargetState) {
+        if target_state.selected_altitude.is_some() {
+            self.selected_altitude = target_state.selected_altitude;
+            self.target_altitude_source = match target_state.target_altitude_source {
+                Some(1) => '\u{2081}',
+                Some(2) => '\u{2082}',
+                Some(3) => '\u{2083}',
+                _ => ' ',
+            };
+        }
+        if target_state.barometric_pressure_setting.is_some() {
+            self.barometric_pressure_setting = target_state.barometric_pressure_setting;
+        }
+        self.selected_heading = target_state.selected_heading;
+        if let Some(mut autopilot_modes) = target_state.autopilot_modes {
+            if autopilot_modes.tcas.is_none() {
+                autopilot_modes.tcas = self.autopilot_modes.and_then(|modes| modes.tcas);
+            }
+            self.autopilot_modes = Some(autopilot_modes);
+        }
+    }
 }
 
 impl Default for Plane {
@@ -145,6 +367,12 @@ impl Display for Plane {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ICAO: {:06X}", self.icao)?;
         write!(f, " Reg: {:2}", self.reg)?;
+        if let Some(registration) = &self.registration {
+            write!(f, " Registration: {:7}", registration)?;
+        }
+        if let Some(aircraft_type) = &self.aircraft_type {
+            write!(f, " Type: {}", aircraft_type)?;
+        }
         if let Some(altitude) = self.altitude {
             write!(f, " Alt: {:>5}", altitude)?;
         } else {
@@ -0,0 +1,173 @@
+use super::Plane;
+use crate::decoder::{Emergency, FlightStatus, ResolutionAdvisory};
+use chrono::{DateTime, Utc};
+use log::error;
+
+/// Seconds a resolution advisory stays in the table after its last broadcast
+const RESOLUTION_ADVISORY_TTL: i64 = 20;
+/// Seconds the alert and SPI of the last flight status stay in the table
+const FLIGHT_STATUS_TTL: i64 = 20;
+/// Seconds an implausible position stays in the table as a possible spoofing or decoder fault
+const POSITION_REJECTION_TTL: i64 = 60;
+
+impl Plane {
+    /// Stores the emergency state of TC 28 subtype 1, logging declared and cancelled emergencies.
+    pub(super) fn update_emergency(&mut self, emergency: Option<Emergency>, squawk: Option<u32>) {
+        if emergency.is_some() && emergency != self.emergency {
+            match emergency {
+                Some(Emergency::None) if self.emergency.is_some() => {
+                    error!("{:06X} emergency cancelled", self.icao)
+                }
+                Some(Emergency::None) => {}
+                Some(emergency) => error!(
+                    "{:06X} emergency: {}, squawk {}",
+                    self.icao,
+                    emergency,
+                    squawk.map(|v| format!("{:04}", v)).unwrap_or_default()
+                ),
+                None => {}
+            }
+            self.emergency = emergency;
+        }
+        if squawk.is_some_and(|v| v != 0) {
+            self.squawk = squawk;
+        }
+    }
+
+    /// Stores the ACAS resolution advisory of TC 28 subtype 2, logging new and terminated advisories.
+    pub(super) fn update_resolution_advisory(
+        &mut self,
+        resolution_advisory: Option<ResolutionAdvisory>,
+    ) {
+        let Some(resolution_advisory) = resolution_advisory else {
+            return;
+        };
+        if self.resolution_advisory != Some(resolution_advisory) {
+            error!("{:06X} ACAS RA: {}", self.icao, resolution_advisory);
+        }
+        self.resolution_advisory = Some(resolution_advisory);
+        self.resolution_advisory_timestamp = Some(self.timestamp);
+    }
+
+    /// Stores the flight status of a surveillance reply, logging IDENT and Mode A code change alerts.
+    pub(super) fn update_flight_status(&mut self, flight_status: Option<FlightStatus>) {
+        let Some(flight_status) = flight_status else {
+            return;
+        };
+        let last = self.flight_status.filter(|_| {
+            self.flight_status_timestamp.is_some_and(|timestamp| {
+                self.timestamp
+                    .signed_duration_since(timestamp)
+                    .num_seconds()
+                    < FLIGHT_STATUS_TTL
+            })
+        });
+        if flight_status.spi && !last.is_some_and(|last| last.spi) {
+            error!("{:06X} IDENT", self.icao);
+        }
+        if flight_status.alert && !last.is_some_and(|last| last.alert) {
+            error!(
+                "{:06X} Mode A code changed, squawk {}",
+                self.icao,
+                self.squawk.map(|v| format!("{:04}", v)).unwrap_or_default()
+            );
+        }
+        if flight_status.on_ground.is_some() {
+            self.on_ground = flight_status.on_ground;
+        }
+        self.flight_status = Some(flight_status);
+        self.flight_status_timestamp = Some(self.timestamp);
+    }
+
+    /// Returns the alert shown in the table, a recent resolution advisory takes precedence over the emergency state,
+    /// the emergency state over a recently rejected position and that over the IDENT and alert of the flight status.
+    pub fn alert(&self, now: DateTime<Utc>) -> Option<&'static str> {
+        self.resolution_advisory
+            .zip(self.resolution_advisory_timestamp)
+            .filter(|(_, timestamp)| {
+                now.signed_duration_since(*timestamp).num_seconds() < RESOLUTION_ADVISORY_TTL
+            })
+            .map(|(resolution_advisory, _)| resolution_advisory.abbreviation())
+            .or_else(|| {
+                self.emergency
+                    .filter(|emergency| *emergency != Emergency::None)
+                    .map(|emergency| emergency.abbreviation())
+            })
+            .or_else(|| {
+                self.position_rejected_timestamp
+                    .filter(|timestamp| {
+                        now.signed_duration_since(*timestamp).num_seconds() < POSITION_REJECTION_TTL
+                    })
+                    .map(|_| "JUMP")
+            })
+            .or_else(|| {
+                self.flight_status
+                    .zip(self.flight_status_timestamp)
+                    .filter(|(_, timestamp)| {
+                        now.signed_duration_since(*timestamp).num_seconds() < FLIGHT_STATUS_TTL
+                    })
+                    .and_then(|(flight_status, _)| match flight_status {
+                        FlightStatus { spi: true, .. } => Some("IDNT"),
+                        FlightStatus { alert: true, .. } => Some("ALRT"),
+                        _ => None,
+                    })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{DF, Downlink, get_message};
+    use chrono::Duration;
+
+    #[test]
+    fn test_alert() {
+        let now = Utc::now();
+        let mut plane = Plane::new();
+        plane.timestamp = now;
+        for squitter in [
+            "8D4840D6E12AAA000000003CF5CE",
+            "8D40621DE2C20005210358D28CB5",
+        ] {
+            let message = get_message(squitter).expect("Failed to get message");
+            plane.update(&message, 17, now, false);
+        }
+        assert_eq!(plane.squawk, Some(7700));
+        assert_eq!(plane.emergency, Some(Emergency::General));
+        assert_eq!(plane.alert(now), Some("RA\u{2191}"));
+        assert_eq!(plane.alert(now + Duration::seconds(30)), Some("EMRG"));
+    }
+
+    #[test]
+    fn test_flight_status_alert() {
+        let now = Utc::now();
+        let mut plane = Plane::new();
+        let message = get_message("2D01B89A8E0F41").expect("Failed to get message");
+        plane.update(&message, 5, now, false);
+        assert_eq!(plane.squawk, Some(5611));
+        assert_eq!(plane.alert(now), Some("IDNT"));
+        assert_eq!(plane.on_ground, None);
+        assert_eq!(plane.alert(now + Duration::seconds(30)), None);
+
+        let message = get_message("A20015B7E8AA2F327FD7FF0C302B").expect("Failed to get message");
+        plane.update(&message, 20, now, false);
+        assert_eq!(plane.alert(now), Some("ALRT"));
+        assert_eq!(plane.on_ground, Some(false));
+    }
+
+    #[test]
+    fn test_air_air_resolution_advisory() {
+        let now = Utc::now();
+        let message = get_message("80E1969030C200052103588658DD").expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        let icao = downlink.icao().expect("Failed to get ICAO");
+        let plane = Plane::from_downlink(&downlink, icao, now);
+        assert_eq!(plane.alert(now), Some("RA\u{2191}"));
+        assert_eq!(plane.altitude, Some(35000));
+        assert_eq!(
+            plane.autopilot_modes.and_then(|modes| modes.tcas),
+            Some(true)
+        );
+    }
+}
@@ -20,6 +20,12 @@ impl UpdateFromDownlink<Ext> for Plane {
                 20..=22 => {
                     self.amend_from_ext_20_22(dl);
                 }
+                28 => {
+                    self.amend_from_ext_28(dl);
+                }
+                29 => {
+                    self.amend_from_ext_29(dl);
+                }
                 31 => {
                     self.amend_from_ext_31(dl);
                 }
@@ -39,17 +45,23 @@ impl Plane {
 
     fn amend_from_ext_5_8(&mut self, dl: &Ext) {
         self.ground_movement = dl.ground_movement;
+        self.on_ground = Some(true);
+        self.grspeed = self.ground_movement.map(|speed| speed.round() as u32);
         self.altitude = dl.altitude;
         self.altitude_source = '\u{2070}';
         self.track = dl.track;
         self.track_source = dl.track_source.unwrap_or(' ');
+        self.update_position_integrity(dl.message_type.0, false);
         self.amend_cpr(dl);
     }
 
     fn amend_from_ext_9_18(&mut self, dl: &Ext) {
         self.altitude = dl.altitude;
         self.altitude_source = ' ';
+        self.ground_movement = None;
+        self.on_ground = Some(false);
         self.surveillance_status = dl.surveillance_status.unwrap_or(' ');
+        self.update_position_integrity(dl.message_type.0, dl.nic_supplement_b);
         self.amend_cpr(dl);
     }
 
@@ -63,11 +75,11 @@ impl Plane {
         }
         match dl.message_type.1 {
             1 => {
-                (self.track, self.grspeed) = (self.track, self.grspeed);
+                (self.track, self.grspeed) = (dl.track, dl.grspeed);
                 self.track_source = '\u{2081}';
             }
             2 => {
-                (self.track, self.grspeed) = (self.track, self.grspeed);
+                (self.track, self.grspeed) = (dl.track, dl.grspeed);
                 self.track_source = '\u{2082}';
             }
             3 | 4 => {
@@ -84,8 +96,25 @@ impl Plane {
         self.surveillance_status = dl.surveillance_status.unwrap_or(' ');
     }
 
+    fn amend_from_ext_28(&mut self, dl: &Ext) {
+        match dl.message_type.1 {
+            1 => self.update_emergency(dl.emergency, dl.squawk),
+            2 => self.update_resolution_advisory(dl.resolution_advisory),
+            _ => {}
+        }
+    }
+
+    fn amend_from_ext_29(&mut self, dl: &Ext) {
+        if let Some(target_state) = &dl.target_state {
+            self.update_target_state(target_state);
+        }
+    }
+
     fn amend_from_ext_31(&mut self, dl: &Ext) {
         self.adsb_version = dl.adsb_version;
+        if dl.operational_status.is_some() {
+            self.operational_status = dl.operational_status;
+        }
     }
 
     fn amend_cpr(&mut self, dl: &Ext) {
@@ -98,3 +127,20 @@ impl Plane {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{DF, Downlink, get_message};
+
+    #[test]
+    fn test_ground_velocity() {
+        let mut plane = Plane::new();
+        let message = get_message("8D485020994409940838175B284F").expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        plane.update_from_downlink(&downlink);
+        assert_eq!(plane.grspeed, Some(159));
+        assert_eq!(plane.track, Some(182));
+        assert_eq!(plane.track_source, '\u{2081}');
+    }
+}
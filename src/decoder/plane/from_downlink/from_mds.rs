@@ -5,5 +5,33 @@ impl UpdateFromDownlink<Mds> for Plane {
         if let Some(v) = dl.icao {
             self.icao = v;
         }
+        self.update_flight_status(dl.flight_status);
+        if dl.threat_encounter.is_some() {
+            self.threat_encounter = dl.threat_encounter;
+        }
+        self.update_resolution_advisory(dl.resolution_advisory);
+        self.update_data_link_capability(dl.data_link_capability);
+        self.update_mcp_modes(dl.autopilot_modes);
+        if dl.registration.is_some() {
+            self.registration.clone_from(&dl.registration);
+        }
+        if dl.next_waypoint.is_some() {
+            self.next_waypoint.clone_from(&dl.next_waypoint);
+            self.waypoint_timestamps[0] = Some(self.timestamp);
+        }
+        if dl.waypoint_position.is_some() {
+            self.waypoint_position = dl.waypoint_position;
+            self.waypoint_timestamps[1] = Some(self.timestamp);
+        }
+        if dl.waypoint_bearing.is_some() {
+            self.waypoint_bearing = dl.waypoint_bearing;
+            self.waypoint_timestamps[2] = Some(self.timestamp);
+        }
+        if dl.aircraft_type.is_some() {
+            self.aircraft_type.clone_from(&dl.aircraft_type);
+        }
+        if dl.capability.is_some() {
+            self.capability.1 = dl.capability;
+        }
     }
 }
@@ -3,13 +3,16 @@ use crate::decoder::{Plane, Srt, plane::from_downlink::UpdateFromDownlink};
 impl UpdateFromDownlink<Srt> for Plane {
     fn update_from_downlink(&mut self, dl: &Srt) {
         if dl.icao.is_some() {
-            if dl.df == Some(4) && dl.altitude.is_some() {
+            if matches!(dl.df, Some(0 | 4 | 16)) && dl.altitude.is_some() {
                 self.altitude = dl.altitude;
                 self.altitude_source = ' ';
             }
             if dl.df == Some(5) && dl.squawk.is_some() {
                 self.squawk = dl.squawk;
             }
+            self.update_flight_status(dl.flight_status);
+            self.update_air_air(dl.air_air);
+            self.update_resolution_advisory(dl.resolution_advisory);
             if dl.df == Some(11)
                 && let Some(v) = dl.capability
             {
@@ -1,21 +1,28 @@
 use super::Plane;
-use chrono::Utc;
+use crate::decoder;
+use chrono::{DateTime, Utc};
 mod from_bcast;
 mod from_ext;
 mod from_mode_s;
 
 impl Plane {
-    pub fn update(&mut self, message: &[u32], df: u32, relaxed: bool) {
-        self.timestamp = Utc::now();
+    pub fn update(&mut self, message: &[u32], df: u32, now: DateTime<Utc>, relaxed: bool) {
+        self.timestamp = now;
         self.last_df = df;
 
         self.update_from_bcast(message, df);
 
-        if df == 17 || df == 18 {
+        if df == 17 || (df == 18 && decoder::is_extended_squitter(message)) {
             self.update_from_ext(message, df);
         }
 
-        if (relaxed || (self.capability.0 > 3)) && (df == 20 || df == 21) {
+        if (relaxed
+            || self.capability.0 > 3
+            || self
+                .data_link_capability
+                .is_some_and(|c| c.specific_services))
+            && (df == 20 || df == 21)
+        {
             self.update_from_mode_s(message, df, relaxed);
         }
     }
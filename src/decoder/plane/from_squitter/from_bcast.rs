@@ -3,13 +3,22 @@ use crate::decoder;
 
 impl Plane {
     pub(super) fn update_from_bcast(&mut self, message: &[u32], df: u32) {
-        if df == 4 || df == 20 {
+        if matches!(df, 0 | 4 | 16 | 20) {
             self.altitude = decoder::altitude(message, df);
             self.altitude_source = ' ';
         }
         if df == 5 || df == 21 {
             self.squawk = decoder::squawk(message);
         }
+        if matches!(df, 4 | 5 | 20 | 21) {
+            self.update_flight_status(decoder::flight_status(message));
+        }
+        if df == 0 || df == 16 {
+            self.update_air_air(decoder::air_air_surveillance(message, df));
+        }
+        if df == 16 {
+            self.update_resolution_advisory(decoder::mv_resolution_advisory(message));
+        }
 
         if df == 11 || df == 17 {
             self.capability.0 = decoder::get_capability(message);
@@ -24,6 +24,12 @@ impl Plane {
             20..=22 => {
                 self.update_from_ext_20_22(message);
             }
+            28 => {
+                self.update_from_ext_28(message, message_subtype);
+            }
+            29 => {
+                self.update_from_ext_29(message);
+            }
             31 => {
                 self.update_from_ext_31(message);
             }
@@ -55,17 +61,23 @@ impl Plane {
 
     pub(super) fn update_from_ext_5_8(&mut self, message: &[u32], message_type: u32) {
         self.ground_movement = decoder::ground_movement(message);
+        self.on_ground = Some(true);
+        self.grspeed = self.ground_movement.map(|speed| speed.round() as u32);
         self.altitude = None;
         self.altitude_source = '\u{2070}';
         self.track = decoder::ground_track(message);
         self.track_source = ' ';
+        self.update_position_integrity(message_type, false);
         self.update_cpr(message, message_type);
     }
 
     pub(super) fn update_from_ext_9_18(&mut self, message: &[u32], message_type: u32, df: u32) {
         self.altitude = decoder::altitude(message, df);
         self.altitude_source = ' ';
+        self.ground_movement = None;
+        self.on_ground = Some(false);
         self.surveillance_status = decoder::surveillance_status(message);
+        self.update_position_integrity(message_type, decoder::nic_supplement_b(message));
         self.update_cpr(message, message_type);
     }
 
@@ -101,7 +113,31 @@ impl Plane {
         self.surveillance_status = decoder::surveillance_status(message);
     }
 
+    pub(super) fn update_from_ext_28(&mut self, message: &[u32], message_subtype: u32) {
+        match message_subtype {
+            1 => {
+                self.update_emergency(
+                    decoder::emergency_state(message),
+                    decoder::emergency_squawk(message),
+                );
+            }
+            2 => {
+                self.update_resolution_advisory(decoder::resolution_advisory(message));
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) fn update_from_ext_29(&mut self, message: &[u32]) {
+        if let Some(target_state) = decoder::target_state(message) {
+            self.update_target_state(&target_state);
+        }
+    }
+
     pub(super) fn update_from_ext_31(&mut self, message: &[u32]) {
         self.adsb_version = decoder::version(message);
+        if let Some(operational_status) = decoder::operational_status(message) {
+            self.operational_status = Some(operational_status);
+        }
     }
 }
@@ -9,96 +9,118 @@ impl Plane {
         if bds == (2, 0) {
             self.ais = decoder::ais(message);
         }
+        if bds == (2, 5)
+            && let Some(aircraft_type) = decoder::bds_2_5(message)
+        {
+            self.aircraft_type = Some(aircraft_type);
+        }
         if bds == (3, 0) {
             self.threat_encounter = decoder::threat_encounter(message);
+            self.update_resolution_advisory(decoder::bds_3_0(message));
+        }
+        if bds == (1, 0) {
+            let capability = decoder::bds_1_0(message);
+            if let Some(c) = capability {
+                debug!("DF:{}, BDS:1.0 {}", df, c);
+            }
+            self.update_data_link_capability(capability);
         }
         if bds == (0, 0)
             && let Some(result) = decoder::is_bds_1_7(message)
         {
-            self.capability.1 = result;
             bds = (1, 7);
             debug!("Relaxed:{}", relaxed);
             debug!(
-                "DF:{}, BDS:{}.{}, C:{:b} 4:{} 4.4:{} 5:{} 6:{}",
-                df,
-                bds.0,
-                bds.1,
-                self.capability.1.flags,
-                self.capability.1.bds40,
-                self.capability.1.bds44,
-                self.capability.1.bds50,
-                self.capability.1.bds60
+                "DF:{}, BDS:{}.{}, C:{:b} {}",
+                df, bds.0, bds.1, result.flags, result
             );
+            self.capability.1 = Some(result);
         }
         if bds == (0, 0) {
-            if (relaxed || self.capability.1.bds40)
-                && let Some(value) = decoder::is_bds_4_0(message)
-            {
-                self.selected_altitude =
-                    value.mcp_selected_altitude.or(value.fms_selected_altitude);
-                self.target_altitude_source = match value.target_altitude_source {
-                    Some(v) => match v {
-                        1 => '\u{2081}',
-                        2 => '\u{2082}',
-                        3 => '\u{2083}',
-                        _ => ' ',
-                    },
-                    _ => ' ',
-                };
-                self.barometric_pressure_setting = value.barometric_pressure_setting;
-                bds = (4, 0);
+            let (state, capability) = self.comm_b_context(relaxed);
+            if let Some(inference) = decoder::infer_bds(message, &state, capability.as_ref()) {
+                bds = inference.register.bds();
                 debug!(
-                    "DF:{}, BDS:{}.{} S:{}",
-                    df,
-                    bds.0,
-                    bds.1,
-                    value.target_altitude_source.unwrap_or(0)
+                    "DF:{}, BDS:{}.{} score:{:.2} confidence:{:.2} candidates:{}",
+                    df, bds.0, bds.1, inference.score, inference.confidence, inference.candidates
                 );
-            } else if (relaxed || self.capability.1.bds50)
-                && let Some(result) = decoder::is_bds_5_0(message)
-            {
-                self.roll_angle = result.roll_angle;
-                self.track = result.track_angle;
-                self.track_angle_rate = result.track_angle_rate;
-                self.grspeed = result.ground_speed;
-                self.true_airspeed = result.true_airspeed;
-                self.bds_5_0_timestamp = Some(self.timestamp);
-                self.track_source = '\u{2085}';
-                self.track_timestamp = Some(self.timestamp);
-                bds = (5, 0);
-                debug!("DF:{}, BDS:{}.{}", df, bds.0, bds.1);
-            } else if (relaxed || self.capability.1.bds60)
-                && let Some(result) = decoder::is_bds_6_0(message)
-            {
-                self.heading = result.magnetic_heading;
-                self.indicated_airspeed = result.indicated_airspeed;
-                self.mach_number = result.mach_number;
-                self.vrate = match result.barometric_altitude_rate.is_some() {
-                    true => {
-                        self.vrate_source = '\u{2086}';
-                        result.barometric_altitude_rate
+                match inference.register {
+                    decoder::CommB::SelectedVerticalIntention(value) => {
+                        self.selected_altitude =
+                            value.mcp_selected_altitude.or(value.fms_selected_altitude);
+                        self.target_altitude_source = match value.target_altitude_source {
+                            Some(v) => match v {
+                                1 => '\u{2081}',
+                                2 => '\u{2082}',
+                                3 => '\u{2083}',
+                                _ => ' ',
+                            },
+                            _ => ' ',
+                        };
+                        self.barometric_pressure_setting = value.barometric_pressure_setting;
+                        self.update_mcp_modes(value.autopilot_modes);
+                        debug!(
+                            "DF:{}, BDS:{}.{} S:{}",
+                            df,
+                            bds.0,
+                            bds.1,
+                            value.target_altitude_source.unwrap_or(0)
+                        );
+                    }
+                    decoder::CommB::TrackAndTurn(result) => {
+                        self.roll_angle = result.roll_angle;
+                        self.track = result.track_angle;
+                        self.track_angle_rate = result.track_angle_rate;
+                        self.grspeed = result.ground_speed;
+                        self.true_airspeed = result.true_airspeed;
+                        self.bds_5_0_timestamp = Some(self.timestamp);
+                        self.track_source = '\u{2085}';
+                        self.track_timestamp = Some(self.timestamp);
+                    }
+                    decoder::CommB::HeadingAndSpeed(result) => {
+                        self.heading = result.magnetic_heading;
+                        self.indicated_airspeed = result.indicated_airspeed;
+                        self.mach_number = result.mach_number;
+                        self.vrate = match result.barometric_altitude_rate.is_some() {
+                            true => {
+                                self.vrate_source = '\u{2086}';
+                                result.barometric_altitude_rate
+                            }
+                            _ => {
+                                self.vrate_source = '\u{2071}';
+                                result.internal_vertical_velocity
+                            }
+                        };
+                        self.heading_source = '\u{2086}';
+                        self.heading_timestamp = Some(self.timestamp);
+                    }
+                    decoder::CommB::Meteo(meteo) => {
+                        self.temperature = meteo.temp;
+                        if meteo.wind.is_some() {
+                            self.wind = meteo.wind;
+                        }
+                        self.humidity = meteo.humidity;
+                        self.turbulence = meteo.turbulence;
+                        self.pressure = meteo.pressure;
+                        debug!("DF:{} B:4.4 FOM:{:b}", df, message[8] & 0xF);
+                    }
+                    decoder::CommB::Temperature(value) => self.temperature = Some(value),
+                    decoder::CommB::Registration(registration) => {
+                        self.registration = Some(registration)
+                    }
+                    decoder::CommB::NextWaypoint(waypoint) => {
+                        self.next_waypoint = Some(waypoint);
+                        self.waypoint_timestamps[0] = Some(self.timestamp);
+                    }
+                    decoder::CommB::WaypointPosition(position) => {
+                        self.waypoint_position = Some(position);
+                        self.waypoint_timestamps[1] = Some(self.timestamp);
                     }
-                    _ => {
-                        self.vrate_source = '\u{2071}';
-                        result.internal_vertical_velocity
+                    decoder::CommB::WaypointBearing(bearing) => {
+                        self.waypoint_bearing = Some(bearing);
+                        self.waypoint_timestamps[2] = Some(self.timestamp);
                     }
-                };
-                self.heading_source = '\u{2086}';
-                self.heading_timestamp = Some(self.timestamp);
-                bds = (6, 0);
-                debug!("DF:{}, BDS:{}.{}", df, bds.0, bds.1);
-            } else if let Some(meteo) = decoder::is_bds_4_4(message) {
-                self.temperature = meteo.temp;
-                if meteo.wind.is_some() {
-                    self.wind = meteo.wind;
                 }
-                self.humidity = meteo.humidity;
-                self.turbulence = meteo.turbulence;
-                self.pressure = meteo.pressure;
-                bds = (4, 4);
-                debug!("DF:{} B:4.4 FOM:{:b}", df, message[8] & 0xF);
-            } else if let Some(value) = decoder::is_bds_4_5(message) {
-                self.temperature = Some(value)
             }
         }
         debug!("DF:{} BDS:{}.{}", df, bds.0, bds.1);
@@ -8,8 +8,10 @@ impl LegendHeaders {
         let mut headers = vec![
             ("ICAO", 6),
             ("RG", 2),
+            ("REG/TYPE", 12),
             ("SQWK", 4),
             ("W", 1),
+            ("EMRG", 4),
             ("CALLSIGN", 8),
             ("LATITUDE", 9),
             ("LONGITUDE", 11),
@@ -18,7 +20,15 @@ impl LegendHeaders {
         ];
 
         if display_flags.altitude() {
-            headers.extend([("ALT G", 5), ("ALT S", 5), ("BARO", 4)]);
+            headers.extend([
+                ("ALT G", 5),
+                ("ALT S", 5),
+                ("WAYPOINT", 9),
+                ("DTG", 5),
+                ("BARO", 4),
+                ("HDS", 3),
+                ("MODES", 6),
+            ]);
         }
 
         headers.extend([("VRATE", 5), ("TRK", 3), ("HDG", 3), ("GSP", 3)]);
@@ -46,6 +56,7 @@ impl LegendHeaders {
                 ("TC", 2),
                 ("V", 1),
                 ("S", 1),
+                ("NIC", 3),
                 ("PTH", 3),
             ]);
         }
@@ -154,7 +165,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP LC\n"
         )
     }
 
@@ -164,7 +175,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP  TEMP WND WDR HUM PRES TB LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP  TEMP WND WDR HUM PRES TB LC\n"
         )
     }
 
@@ -174,7 +185,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
         )
     }
 
@@ -184,7 +195,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
         )
     }
 
@@ -194,7 +205,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB LC\n"
         )
     }
 
@@ -204,7 +215,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB VX DF TC V S PTH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH RLL TAR  TEMP WND WDR HUM PRES TB VX DF TC V S NIC PTH LC\n"
         )
     }
 
@@ -214,7 +225,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP RLL TAR LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP RLL TAR LC\n"
         )
     }
 
@@ -224,7 +235,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH RLL TAR LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH RLL TAR LC\n"
         )
     }
 
@@ -234,7 +245,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH RLL TAR LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH RLL TAR LC\n"
         )
     }
 
@@ -244,7 +255,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH RLL TAR VX DF TC V S PTH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH RLL TAR VX DF TC V S NIC PTH LC\n"
         )
     }
 
@@ -254,7 +265,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP TAS IAS MACH LC\n"
         )
     }
 
@@ -264,7 +275,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH LC\n"
         )
     }
 
@@ -274,7 +285,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP TAS IAS MACH VX DF TC V S PTH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP TAS IAS MACH VX DF TC V S NIC PTH LC\n"
         )
     }
 
@@ -284,7 +295,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP LC\n"
         )
     }
 
@@ -294,7 +305,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S BARO VRATE TRK HDG GSP VX DF TC V S PTH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B ALT G ALT S  WAYPOINT   DTG BARO HDS  MODES VRATE TRK HDG GSP VX DF TC V S NIC PTH LC\n"
         )
     }
 
@@ -304,7 +315,7 @@ mod tests {
         let headers = LegendHeaders::from_display_flags(&display_flags);
         assert_eq!(
             headers.header,
-            "  ICAO RG SQWK W CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP VX DF TC V S PTH LC\n"
+            "  ICAO RG     REG/TYPE SQWK W EMRG CALLSIGN  LATITUDE   LONGITUDE  DIST ALT B VRATE TRK HDG GSP VX DF TC V S NIC PTH LC\n"
         )
     }
 }
@@ -7,8 +7,12 @@ pub struct Legend {
 impl Legend {
     pub fn from_display_flags(display_flags: &DisplayFlags) -> Self {
         let legend = [
-            ("ICAO", "ICAO Address"),
+            ("ICAO", "ICAO Address, t/T TIS-B, r/R ADS-R, ~ non-ICAO"),
             ("RG", "Registraton Country Code"),
+            (
+                "REG/TYPE",
+                "Registration (BDS 2.1) and Aircraft Type (BDS 2.5)",
+            ),
             ("ALT B", "Altitude (Barometric)"),
             ("SQWK", "Squawk"),
             ("CALLSIGN", "Callsign"),
@@ -20,8 +24,24 @@ impl Legend {
             ("VRATE", "Vertical Rate"),
             ("LC", "Last Contact"),
             ("W", "Wake Turbulence Category"),
+            (
+                "EMRG",
+                "Emergency, ACAS RA, JUMP implausible position, IDNT Ident, ALRT Mode A change",
+            ),
         ];
 
+        let legend_altitude = [
+            ("ALT G", "Altitude (GNSS)"),
+            ("ALT S", "Selected Altitude"),
+            ("WAYPOINT", "Next Waypoint (BDS 4.1)"),
+            ("DTG", "Distance to Go to the Next Waypoint (BDS 4.3)"),
+            ("BARO", "Barometric Pressure Setting"),
+            ("HDS", "Selected Heading"),
+            (
+                "MODES",
+                "A Autopilot, V VNAV, H Altitude Hold, P Approach, L LNAV, T TCAS",
+            ),
+        ];
         let legend_speed = [
             ("TAS", "True Air Speed"),
             ("IAS", "Indicated Air Speed"),
@@ -43,6 +63,7 @@ impl Legend {
             ("TC", "Type Code"),
             ("V", "ASD-B Version"),
             ("S", "Surveillance Status"),
+            ("NIC", "Navigation Integrity Category, \u{1d58} NUCp"),
             ("PTH", "Position, Track, Heaging age"),
         ];
 
@@ -58,6 +79,22 @@ impl Legend {
                     w1 = width.1
                 )
             })
+            .chain(if display_flags.altitude() {
+                legend_altitude
+                    .iter()
+                    .map(|&(header, description)| {
+                        format!(
+                            "{:w0$}: {:w1$}\n",
+                            header,
+                            description,
+                            w0 = width.0,
+                            w1 = width.1
+                        )
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            })
             .chain(if display_flags.speed() {
                 legend_speed
                     .iter()
@@ -1,16 +1,38 @@
 use super::{Plane, header::DisplayFlags};
 use crate::decoder;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::fmt;
 
 pub trait SimpleDisplay {
-    fn simple_display(&self, f: &mut fmt::Formatter, display_flags: &DisplayFlags) -> fmt::Result;
+    fn simple_display(
+        &self,
+        f: &mut fmt::Formatter,
+        display_flags: &DisplayFlags,
+        now: DateTime<Utc>,
+    ) -> fmt::Result;
 }
 
 impl SimpleDisplay for Plane {
-    fn simple_display(&self, f: &mut fmt::Formatter, display_flags: &DisplayFlags) -> fmt::Result {
-        write!(f, "{:06X} ", self.icao)?;
+    fn simple_display(
+        &self,
+        f: &mut fmt::Formatter,
+        display_flags: &DisplayFlags,
+        now: DateTime<Utc>,
+    ) -> fmt::Result {
+        write!(f, "{:06X}{}", self.icao, self.address_type.marker())?;
         write!(f, "{:2} ", self.reg)?;
+        write!(
+            f,
+            "{:7} {:4} ",
+            self.registration
+                .as_ref()
+                .and_then(|r| r.aircraft.as_deref())
+                .unwrap_or_default(),
+            self.aircraft_type
+                .as_ref()
+                .map(|t| t.designator.as_str())
+                .unwrap_or_default()
+        )?;
         if let Some(squawk) = self.squawk {
             write!(f, "{:04}", squawk)?;
         } else {
@@ -26,6 +48,7 @@ impl SimpleDisplay for Plane {
         } else {
             write!(f, "  ")?;
         }
+        write!(f, "{:4} ", self.alert(now).unwrap_or_default())?;
         if let Some(ais) = &self.ais {
             write!(f, "{:8} ", ais)?;
         } else {
@@ -58,11 +81,28 @@ impl SimpleDisplay for Plane {
             } else {
                 write!(f, "{:5} ", "")?;
             }
+            let (next_waypoint, _, waypoint_bearing) = self.route_intent(now);
+            write!(f, "{:>9} ", next_waypoint.unwrap_or_default())?;
+            if let Some(distance) = waypoint_bearing.and_then(|v| v.distance) {
+                write!(f, "{:>5.0} ", distance)?;
+            } else {
+                write!(f, "{:5} ", "")?;
+            }
             if let Some(value) = self.barometric_pressure_setting {
                 write!(f, "{:>4} ", value)?;
             } else {
                 write!(f, "{:4} ", "")?;
             }
+            if let Some(selected_heading) = self.selected_heading {
+                write!(f, "{:>3} ", selected_heading)?;
+            } else {
+                write!(f, "{:3} ", "")?;
+            }
+            if let Some(autopilot_modes) = self.autopilot_modes {
+                write!(f, "{} ", autopilot_modes)?;
+            } else {
+                write!(f, "{:6} ", "")?;
+            }
         }
         if let Some(vrate) = self.vrate {
             write!(f, "{:>5}", vrate)?;
@@ -162,15 +202,16 @@ impl SimpleDisplay for Plane {
                 write!(f, "{:1} ", "")?;
             }
             write!(f, "{} ", self.surveillance_status)?;
+            if let Some(position_integrity) = self.position_integrity {
+                write!(f, "{} ", position_integrity)?;
+            } else {
+                write!(f, "{:3} ", "")?;
+            }
             if let Some(position_timestamp) = self.position_timestamp {
                 write!(
                     f,
                     "{:X}",
-                    (Utc::now()
-                        .signed_duration_since(position_timestamp)
-                        .num_seconds()
-                        / 10)
-                        & 15
+                    (now.signed_duration_since(position_timestamp).num_seconds() / 10) & 15
                 )?;
             } else {
                 write!(f, " ")?;
@@ -179,11 +220,7 @@ impl SimpleDisplay for Plane {
                 write!(
                     f,
                     "{:X}",
-                    (Utc::now()
-                        .signed_duration_since(track_timestamp)
-                        .num_seconds()
-                        / 10)
-                        & 15
+                    (now.signed_duration_since(track_timestamp).num_seconds() / 10) & 15
                 )?;
             } else {
                 write!(f, " ")?;
@@ -192,11 +229,7 @@ impl SimpleDisplay for Plane {
                 write!(
                     f,
                     "{:X} ",
-                    (Utc::now()
-                        .signed_duration_since(heading_timestamp)
-                        .num_seconds()
-                        / 10)
-                        & 15
+                    (now.signed_duration_since(heading_timestamp).num_seconds() / 10) & 15
                 )?;
             } else {
                 write!(f, "  ")?;
@@ -205,21 +238,23 @@ impl SimpleDisplay for Plane {
         write!(
             f,
             "{:>2}",
-            Utc::now()
-                .signed_duration_since(self.timestamp)
-                .num_seconds()
+            now.signed_duration_since(self.timestamp).num_seconds()
         )
     }
 }
 
-pub struct SimpleDisplayWrapper<'a, T: SimpleDisplay>(&'a T, &'a DisplayFlags);
+pub struct SimpleDisplayWrapper<'a, T: SimpleDisplay>(&'a T, &'a DisplayFlags, DateTime<Utc>);
 
 impl<T: SimpleDisplay> fmt::Display for SimpleDisplayWrapper<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.simple_display(f, self.1)
+        self.0.simple_display(f, self.1, self.2)
     }
 }
 
-pub fn format_simple_display<T: SimpleDisplay>(item: &T, display_flags: &DisplayFlags) -> String {
-    format!("{}", SimpleDisplayWrapper(item, display_flags))
+pub fn format_simple_display<T: SimpleDisplay>(
+    item: &T,
+    display_flags: &DisplayFlags,
+    now: DateTime<Utc>,
+) -> String {
+    format!("{}", SimpleDisplayWrapper(item, display_flags, now))
 }
@@ -1,6 +1,39 @@
 use super::Plane;
 use crate::decoder;
+use lazy_static::lazy_static;
+use log::{debug, error};
 use std::f64::consts::PI;
+use std::sync::Mutex;
+
+/// Seconds the last known position serves as the reference for local decoding
+const LOCAL_REFERENCE_AGE: i64 = 60;
+/// Distance (km) above which global and local decoding are considered in disagreement
+const CPR_AGREEMENT_KM: f64 = 0.5;
+/// Seconds between the even and odd formats for a global decoding, surface / airborne
+const CPR_PAIR_AGE: [i64; 2] = [25, 10];
+/// Ground speed (kt) assumed for the plausibility check while unknown, surface / airborne
+const MAX_GROUND_SPEED: [u32; 2] = [100, 1000];
+/// Distance (km) a position may always move, covering the CPR resolution and timestamp jitter
+const POSITION_SLACK_KM: f64 = 1.0;
+/// Distance (km) from the observer within which local decoding relative to it is unambiguous,
+/// half a latitude zone, surface / airborne
+const OBSERVER_RANGE_KM: [f64; 2] = [45.0 * 1.852, 180.0 * 1.852];
+
+lazy_static! {
+    static ref POSITION_MARGIN: Mutex<f64> = Mutex::new(0.0);
+}
+
+/// Rejects positions farther from the last confirmed one than the ground speed times `margin` allows
+/// since it was received, 0 disables the check.
+pub fn set_position_margin(margin: f64) {
+    *POSITION_MARGIN.lock().expect("Cannot set position margin.") = margin.max(0.0);
+}
+
+fn position_margin() -> f64 {
+    *POSITION_MARGIN
+        .lock()
+        .expect("Failed to lock position margin")
+}
 
 /// Updates the position of the plane based on the received message type and CPR format.
 ///
@@ -11,32 +44,61 @@ use std::f64::consts::PI;
 ///
 /// # Remarks
 ///
-/// This method updates the position of the plane if the following conditions are met:
-/// - The CPR latitude and longitude values are not equal to zero.
-/// - The time difference between the CPR timestamps is less than 10 seconds.
+/// The position is decoded globally when both CPR formats are known and less than 10 seconds apart
+/// (25 seconds for surface positions, whose 90° zones are resolved with the local reference),
+/// and locally from the last frame relative to the last globally confirmed position of the plane or,
+/// without one, to the observer. When both are available they have to agree, unless the local
+/// reference is the observer and the plane is beyond the unambiguous range (180 NM airborne,
+/// 45 NM on the surface), where a position relative to the observer alone is rejected.
 ///
-/// If the conditions are met and the calculated latitude and longitude values are within the valid range,
-/// the plane's latitude, longitude, and position timestamp are updated.
+/// If a position is decoded and the latitude and longitude values are within the valid range,
+/// the plane's latitude, longitude, and position timestamp are updated, unless the plane could
+/// not have reached it from its last confirmed position. A rejected position is counted and
+/// discards the other CPR format, so that the next position is decoded from a fresh pair.
 ///
 impl Plane {
     pub(super) fn update_position(&mut self, message_type: u32, cpr_form: u32) {
-        if self.cpr_lat[0] != 0
-            && self.cpr_lat[1] != 0
-            && self.cpr_lon[0] != 0
-            && self.cpr_lon[1] != 0
-            && self.cpr_time[0]
-                .signed_duration_since(self.cpr_time[1])
-                .num_seconds()
-                .abs()
-                < 10
-            && let Some((lat, lon)) = match message_type {
-                5..=8 => decoder::cpr_location(&self.cpr_lat, &self.cpr_lon, cpr_form, 4),
-                9..=18 => decoder::cpr_location(&self.cpr_lat, &self.cpr_lon, cpr_form, 1),
-                _ => None,
+        let global = self.global_position(message_type, cpr_form);
+        let (local, observer_reference) = match message_type {
+            5..=8 => self.local_position(cpr_form, true),
+            9..=18 => self.local_position(cpr_form, false),
+            _ => (None, false),
+        };
+        let observer = decoder::observer::get_observer_coords().filter(|_| observer_reference);
+        let in_range = |position| {
+            observer.is_none_or(|observer| within_observer_range(observer, position, message_type))
+        };
+
+        let position = match (global, local) {
+            (Some(global), Some(local))
+                if in_range(global)
+                    && haversine(global.0, global.1, local.0, local.1) > CPR_AGREEMENT_KM =>
+            {
+                debug!(
+                    "{:06X} global {:?} and local {:?} positions disagree",
+                    self.icao, global, local
+                );
+                None
+            }
+            (Some(global), _) => Some(global),
+            (None, Some(local)) if !in_range(local) => {
+                debug!(
+                    "{:06X} local {:?} position beyond the observer range",
+                    self.icao, local
+                );
+                None
             }
+            (None, local) => local,
+        };
+
+        if let Some((lat, lon)) = position
             && (-90.0..=90.0).contains(&lat)
             && (-180.0..=180.0).contains(&lon)
         {
+            if !self.is_plausible((lat, lon), message_type, position_margin()) {
+                self.reject_position((lat, lon), cpr_form);
+                return;
+            }
             self.lat = lat;
             self.lon = lon;
             if let Some(observer) = decoder::observer::get_observer_coords() {
@@ -44,8 +106,133 @@ impl Plane {
                     Some(haversine(self.lat, self.lon, observer.0, observer.1));
             };
             self.position_timestamp = Some(self.timestamp);
+            self.position_confirmed = global.is_some() || !observer_reference;
+        }
+    }
+
+    /// Distance (km) to another plane, `None` unless both positions are known.
+    pub fn distance_to(&self, other: &Plane) -> Option<f64> {
+        (self.position_timestamp.is_some() && other.position_timestamp.is_some())
+            .then(|| haversine(self.lat, self.lon, other.lat, other.lon))
+    }
+
+    /// Checks that the position is reachable from the last confirmed one at the ground speed,
+    /// any position is while the elapsed time is unknown or 0.
+    fn is_plausible(&self, position: (f64, f64), message_type: u32, margin: f64) -> bool {
+        let Some(position_timestamp) = self.position_timestamp else {
+            return true;
+        };
+        if !self.position_confirmed || !self.timestamp_known || margin == 0.0 {
+            return true;
+        }
+        let elapsed = self
+            .timestamp
+            .signed_duration_since(position_timestamp)
+            .num_milliseconds() as f64
+            / 1000.0;
+        if elapsed <= 0.0 {
+            return true;
         }
+        let speed = self.grspeed.unwrap_or(match message_type {
+            5..=8 => MAX_GROUND_SPEED[0],
+            _ => MAX_GROUND_SPEED[1],
+        }) as f64;
+        let reach = speed * 1.852 / 3600.0 * elapsed * margin + POSITION_SLACK_KM;
+        haversine(self.lat, self.lon, position.0, position.1) <= reach
+    }
+
+    /// Counts an implausible position and drops the confirmation and the other CPR format.
+    fn reject_position(&mut self, position: (f64, f64), cpr_form: u32) {
+        error!(
+            "{:06X} implausible position {:.5}, {:.5}, {:.1} km from the last one",
+            self.icao,
+            position.0,
+            position.1,
+            haversine(self.lat, self.lon, position.0, position.1)
+        );
+        self.position_rejections += 1;
+        self.position_rejected_timestamp = Some(self.timestamp);
+        self.position_confirmed = false;
+        let other = 1 - cpr_form as usize;
+        self.cpr_lat[other] = 0;
+        self.cpr_lon[other] = 0;
     }
+
+    /// Decodes the position from the even and odd CPR formats.
+    fn global_position(&self, message_type: u32, cpr_form: u32) -> Option<(f64, f64)> {
+        let pair_age = match message_type {
+            5..=8 => CPR_PAIR_AGE[0],
+            _ => CPR_PAIR_AGE[1],
+        };
+        if self.cpr_lat[0] != 0
+            && self.cpr_lat[1] != 0
+            && self.cpr_lon[0] != 0
+            && self.cpr_lon[1] != 0
+            && self.cpr_time[0]
+                .signed_duration_since(self.cpr_time[1])
+                .num_seconds()
+                .abs()
+                < pair_age
+        {
+            match message_type {
+                5..=8 => self.local_reference().and_then(|(reference, _)| {
+                    decoder::cpr_surface_location(reference, &self.cpr_lat, &self.cpr_lon, cpr_form)
+                }),
+                9..=18 => decoder::cpr_location(&self.cpr_lat, &self.cpr_lon, cpr_form),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Decodes the position from the last CPR format relative to a reference position,
+    /// returns whether the reference is the observer.
+    fn local_position(&self, cpr_form: u32, surface: bool) -> (Option<(f64, f64)>, bool) {
+        let form = cpr_form as usize;
+        let Some((reference, observer_reference)) = self.local_reference() else {
+            return (None, false);
+        };
+        if self.cpr_lat[form] == 0 && self.cpr_lon[form] == 0 {
+            return (None, observer_reference);
+        }
+        (
+            decoder::cpr_local_location(
+                reference,
+                cpr_form,
+                self.cpr_lat[form],
+                self.cpr_lon[form],
+                surface,
+            ),
+            observer_reference,
+        )
+    }
+
+    /// The last confirmed position while recent, otherwise the observer.
+    fn local_reference(&self) -> Option<((f64, f64), bool)> {
+        match self.position_timestamp {
+            Some(position_timestamp)
+                if self.position_confirmed
+                    && self
+                        .timestamp
+                        .signed_duration_since(position_timestamp)
+                        .num_seconds()
+                        < LOCAL_REFERENCE_AGE =>
+            {
+                Some(((self.lat, self.lon), false))
+            }
+            _ => decoder::observer::get_observer_coords().map(|observer| (observer, true)),
+        }
+    }
+}
+
+/// Returns `true` if a position decoded relative to the observer is within the unambiguous range.
+fn within_observer_range(observer: (f64, f64), position: (f64, f64), message_type: u32) -> bool {
+    let range = match message_type {
+        5..=8 => OBSERVER_RANGE_KM[0],
+        _ => OBSERVER_RANGE_KM[1],
+    };
+    haversine(observer.0, observer.1, position.0, position.1) <= range
 }
 
 fn degrees_to_radians(degrees: f64) -> f64 {
@@ -53,7 +240,7 @@ fn degrees_to_radians(degrees: f64) -> f64 {
 }
 
 // Haversine formula to calculate the distance between two points
-pub(super) fn haversine(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+pub(crate) fn haversine(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     let r = 6371.0; // Earth radius in kilometers
 
     let lat1 = degrees_to_radians(lat1);
@@ -69,3 +256,68 @@ pub(super) fn haversine(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
 
     r * c
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::get_message;
+    use chrono::Utc;
+
+    #[test]
+    fn test_local_position() {
+        let now = Utc::now();
+        let mut plane = Plane::new();
+        (plane.lat, plane.lon) = (52.2572, 3.9194);
+        plane.position_timestamp = Some(now);
+        plane.position_confirmed = true;
+
+        let message = get_message("8D40621D58C386435CC412692AD6").expect("Failed to get message");
+        plane.update(&message, 17, now, false);
+        assert!((plane.lat - 52.26578).abs() < 1e-4, "{}", plane.lat);
+        assert!((plane.lon - 3.93891).abs() < 1e-4, "{}", plane.lon);
+        assert!(plane.position_confirmed);
+    }
+
+    #[test]
+    fn test_observer_range() {
+        let observer = (52.66, -8.62);
+        assert!(within_observer_range(observer, (54.0, -6.0), 11));
+        assert!(!within_observer_range(observer, (56.0, -4.0), 11));
+        assert!(within_observer_range(observer, (53.0, -8.0), 6));
+        assert!(!within_observer_range(observer, (54.0, -6.0), 6));
+    }
+
+    #[test]
+    fn test_position_plausibility() {
+        let now = Utc::now();
+        let mut plane = Plane::new();
+        (plane.lat, plane.lon) = (52.2572, 3.9194);
+        plane.position_timestamp = Some(now - chrono::Duration::seconds(10));
+        plane.position_confirmed = true;
+        plane.timestamp = now;
+        plane.timestamp_known = true;
+        plane.grspeed = Some(450);
+        plane.cpr_lat = [93000, 74158];
+        plane.cpr_lon = [51372, 50194];
+
+        // 10 s at 450 kt is about 2.3 km, 4.6 km with a margin of 2
+        assert!(plane.is_plausible((52.2572, 3.9550), 17, 2.0));
+        assert!(!plane.is_plausible((52.2572, 4.0194), 17, 2.0));
+        assert!(plane.is_plausible((48.0, 2.0), 17, 0.0));
+
+        // Frames without timing, or with the time of the last position
+        plane.timestamp_known = false;
+        assert!(plane.is_plausible((48.0, 2.0), 17, 2.0));
+        plane.timestamp_known = true;
+        plane.timestamp = now - chrono::Duration::seconds(10);
+        assert!(plane.is_plausible((48.0, 2.0), 17, 2.0));
+        plane.timestamp = now;
+
+        plane.reject_position((48.0, 2.0), 1);
+        assert_eq!(plane.position_rejections, 1);
+        assert!(!plane.position_confirmed);
+        assert_eq!((plane.cpr_lat[0], plane.cpr_lat[1]), (0, 74158));
+        assert_eq!(plane.alert(now), Some("JUMP"));
+        assert!(plane.is_plausible((48.0, 2.0), 17, 2.0));
+    }
+}
@@ -1,51 +1,95 @@
-use log::debug;
+use log::{debug, error};
 
 use crate::AppCounters;
 use crate::Args;
 use chrono::{DateTime, Utc};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{Arc, RwLock},
 };
 
 use super::{
-    DF, UpdateFromDownlink,
+    Capability, DF, Encounter, Frame, KnownState, UpdateFromDownlink, address_type,
     plane::{DisplayFlags, Plane, format_simple_display},
 };
 
+/// Resolution advisories kept for the encounters feed, the oldest are dropped first
+const MAX_ENCOUNTERS: usize = 1000;
+
+#[derive(Clone)]
 pub struct Planes {
     pub aircrafts: Arc<RwLock<HashMap<u32, Plane>>>,
+    /// Reception time of the last frame, the current time of the aircraft state
+    pub(crate) time: Arc<RwLock<DateTime<Utc>>>,
+    /// ACAS resolution advisories in order of reception, linking both aircraft
+    pub encounters: Arc<RwLock<VecDeque<Encounter>>>,
 }
 
 impl Planes {
     pub fn new() -> Self {
         Planes {
             aircrafts: Arc::new(RwLock::new(HashMap::new())),
+            time: Arc::new(RwLock::new(Utc::now())),
+            encounters: Arc::new(RwLock::new(VecDeque::new())),
         }
     }
 
     pub fn update_aircraft(
         &mut self,
         downlink: &DF,
-        message: &[u32],
+        frame: &Frame,
         df: u32,
         icao: u32,
+        now: DateTime<Utc>,
         args: &Args,
     ) {
+        if let Ok(mut time) = self.time.write() {
+            *time = now;
+        }
+        let key = address_type(&frame.message, df).key(icao);
         if let Ok(mut planes) = self.aircrafts.write() {
+            let resolution_advisory = planes.get(&key).and_then(|p| p.resolution_advisory);
             planes
-                .entry(icao)
+                .entry(key)
                 .and_modify(|p| {
+                    p.update_reception(frame, now);
                     if df < 20 && !&args.use_update_method {
                         p.update_from_downlink(downlink)
                     } else {
-                        p.update(message, df, args.relaxed)
+                        p.update(&frame.message, df, now, args.relaxed)
                     }
                 })
-                .or_insert(Plane::from_downlink(downlink, icao));
+                .or_insert_with(|| {
+                    let mut plane = Plane::from_downlink(downlink, icao, now);
+                    plane.update_reception(frame, now);
+                    plane
+                });
+            if planes.get(&key).and_then(|p| p.resolution_advisory) != resolution_advisory
+                && let Some(encounter) = Encounter::new(&planes, key, now)
+                && let Ok(mut encounters) = self.encounters.write()
+            {
+                error!("{}", encounter);
+                if encounters.len() == MAX_ENCOUNTERS {
+                    encounters.pop_front();
+                }
+                encounters.push_back(encounter);
+            }
         }
     }
 
+    /// State and advertised registers of the aircraft `key` its Comm-B replies are inferred against.
+    pub(crate) fn comm_b_context(
+        &self,
+        key: u32,
+        relaxed: bool,
+    ) -> (KnownState, Option<Capability>) {
+        self.aircrafts
+            .read()
+            .ok()
+            .and_then(|planes| planes.get(&key).map(|plane| plane.comm_b_context(relaxed)))
+            .unwrap_or_default()
+    }
+
     pub(crate) fn cleanup(
         &mut self,
         app_state: &mut AppCounters,
@@ -71,7 +115,7 @@ impl Planes {
         };
     }
 
-    pub fn print(&self, args: &Args, display_flags: &DisplayFlags) {
+    pub fn print(&self, args: &Args, display_flags: &DisplayFlags, now: DateTime<Utc>) {
         let planes = self
             .aircrafts
             .read()
@@ -83,7 +127,7 @@ impl Planes {
         print!(
             "{}",
             planes_vector.iter().fold(String::new(), |acc, (_, plane)| {
-                acc + &format!("{}\n", format_simple_display(*plane, display_flags))
+                acc + &format!("{}\n", format_simple_display(*plane, display_flags, now))
             })
         );
     }
@@ -5,11 +5,14 @@ mod ma_code;
 mod me_code;
 
 pub(crate) use calc::*;
+pub use crc::set_error_correction;
 pub(crate) use crc::*;
 pub(crate) use format::*;
 pub(crate) use ma_code::*;
 pub(crate) use me_code::*;
 
+use super::{get_icao, is_known_icao};
+
 /// Converts a squitter string into a vector of u32 values.
 ///
 /// # Arguments
@@ -20,9 +23,60 @@ pub(crate) use me_code::*;
 ///
 /// * `Option<Vec<u32>>` - An Option vector of u32 values representing the converted squitter string.
 pub fn get_message(squitter: &str) -> Option<Vec<u32>> {
-    clean_squitter(squitter)
-        .filter(|message| matches!(message.len(), 14 | 28))
-        .filter(|message| reminder(message) == 0)
+    clean_squitter(squitter).and_then(valid_message)
+}
+
+/// Converts a binary payload (e.g. a Beast frame) into a vector of u32 values.
+///
+/// # Arguments
+///
+/// * `payload` - The raw message bytes.
+///
+/// # Returns
+///
+/// * `Option<Vec<u32>>` - An Option vector of u32 values, one per nibble of the payload.
+pub fn get_message_from_bytes(payload: &[u8]) -> Option<Vec<u32>> {
+    valid_message(bytes_to_message(payload))
+}
+
+pub(crate) fn bytes_to_message(payload: &[u8]) -> Vec<u32> {
+    payload
+        .iter()
+        .flat_map(|&b| [(b >> 4) as u32, (b & 0xF) as u32])
+        .collect()
+}
+
+pub(crate) fn valid_message(message: Vec<u32>) -> Option<Vec<u32>> {
+    checked_message(message).map(|(message, _)| message)
+}
+
+/// Checks the length and parity of a message, repairing DF11/17/18 when enabled.
+///
+/// Address/parity replies are accepted when the ICAO filter knows their address.
+///
+/// # Arguments
+///
+/// * `message` - The message to check.
+///
+/// # Returns
+///
+/// * `Option<(Vec<u32>, u32)>` - The message and the number of repaired bits, `None` if the message is not valid.
+pub(crate) fn checked_message(mut message: Vec<u32>) -> Option<(Vec<u32>, u32)> {
+    if !matches!(message.len(), 14 | 28) || reminder(&message) != 0 {
+        return None;
+    }
+    let repaired = match (range_value(&message, 1, 5)?, message.len()) {
+        // The low 7 bits of the DF11 syndrome carry the interrogator id
+        (11, 14) if syndrome(&message) & !0x7F == 0 => 0,
+        (11, 14) => fix_errors(&mut message, error_correction().min(1))?,
+        (17 | 18, 28) => fix_errors(&mut message, error_correction())?,
+        (11 | 17 | 18, _) => return None,
+        (df @ (0 | 4 | 5 | 16 | 20 | 21), _) if !is_known_icao(get_icao(&message, df)?) => {
+            return None;
+        }
+        _ => 0,
+    };
+    Some((message, repaired))
 }
 
 pub(crate) fn get_hex_message(message: &[u32]) -> String {
@@ -69,6 +123,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_message_from_bytes() {
+        let payload = [
+            0x8D, 0x40, 0x62, 0x1D, 0x58, 0xC3, 0x82, 0xD6, 0x90, 0xC8, 0xAC, 0x28, 0x63, 0xA7,
+        ];
+        assert_eq!(
+            get_message_from_bytes(&payload),
+            get_message("8D40621D58C382D690C8AC2863A7")
+        );
+        assert_eq!(get_message_from_bytes(&payload[..3]), None);
+    }
+
+    #[test]
+    fn test_checked_message() {
+        let mut message = clean_squitter("8D40621D58C382D690C8AC2863A7").expect("Invalid squitter");
+        assert_eq!(checked_message(message.clone()), Some((message.clone(), 0)));
+        message[10] ^= 1;
+        assert_eq!(checked_message(message), None);
+
+        let message = clean_squitter("5D3982A87C156D").expect("Invalid squitter");
+        assert!(checked_message(message).is_some());
+    }
+
     #[test]
     fn test_message_short() {
         let squitter = "02E197B00179C3";
@@ -1,9 +1,34 @@
-use std::vec;
+use std::{collections::HashMap, sync::Mutex, vec};
 
+use lazy_static::lazy_static;
 use log::debug;
 
 use crate::range_value;
 
+/// Number of leading bits (the downlink format) never repaired.
+const DF_BITS: u32 = 5;
+
+lazy_static! {
+    static ref ERROR_CORRECTION: Mutex<u32> = Mutex::new(0);
+    static ref SYNDROMES_56: HashMap<u32, Vec<u32>> = syndrome_table(56, 1);
+    static ref SYNDROMES_112: HashMap<u32, Vec<u32>> = syndrome_table(112, 2);
+}
+
+/// Sets how many flipped bits are repaired in DF11/17/18 frames, 0 disables the correction.
+///
+/// DF17/18 are repaired up to two bits, DF11 at most one bit.
+pub fn set_error_correction(bits: u32) {
+    *ERROR_CORRECTION
+        .lock()
+        .expect("Cannot set error correction.") = bits.min(2);
+}
+
+pub(crate) fn error_correction() -> u32 {
+    *ERROR_CORRECTION
+        .lock()
+        .expect("Failed to lock error correction")
+}
+
 pub(crate) fn get_crc(message: &[u32], df: u32) -> u32 {
     match df {
         0..=15 => crc56(message),
@@ -53,6 +78,77 @@ fn crc56(message: &[u32]) -> u32 {
     data >> 8
 }
 
+/// Calculates the syndrome of a message with a parity/interrogator (PI) field.
+///
+/// The syndrome is the CRC of the data bits XOR the transmitted parity,
+/// zero for an error-free DF17/18 and the interrogator id for DF11.
+pub(crate) fn syndrome(message: &[u32]) -> u32 {
+    let len = (message.len() * 4) as u32;
+    let parity = range_value(message, len - 23, len).expect("Cannot get parity in syndrome.");
+    let crc = match len {
+        56 => crc56(message),
+        _ => crc112(message),
+    };
+    crc ^ parity
+}
+
+/// Repairs up to `max_bits` flipped bits of a message with a PI field.
+///
+/// # Arguments
+///
+/// * `message` - The message, repaired in place.
+/// * `max_bits` - The maximum number of bits to flip.
+///
+/// # Returns
+///
+/// * `Option<u32>` - The number of repaired bits, `None` if the message cannot be repaired.
+pub(crate) fn fix_errors(message: &mut [u32], max_bits: u32) -> Option<u32> {
+    let syndrome = syndrome(message);
+    if syndrome == 0 {
+        return Some(0);
+    }
+
+    let table = match message.len() {
+        14 => &*SYNDROMES_56,
+        _ => &*SYNDROMES_112,
+    };
+    let bits = table
+        .get(&syndrome)
+        .filter(|bits| bits.len() as u32 <= max_bits)?;
+    bits.iter().for_each(|&bit| flip_bit(message, bit));
+    debug!("Repaired bits {:?}", bits);
+    Some(bits.len() as u32)
+}
+
+fn flip_bit(message: &mut [u32], bit: u32) {
+    let index = (bit - 1) as usize;
+    message[index / 4] ^= 0b1000 >> (index % 4);
+}
+
+/// Maps the syndromes of all one- (and two-) bit errors to the flipped bits.
+fn syndrome_table(len: u32, max_bits: u32) -> HashMap<u32, Vec<u32>> {
+    let mut table = HashMap::new();
+    let error = |bits: &[u32]| {
+        let mut message = vec![0; (len / 4) as usize];
+        bits.iter().for_each(|&bit| flip_bit(&mut message, bit));
+        syndrome(&message)
+    };
+
+    for first in DF_BITS + 1..=len {
+        table.entry(error(&[first])).or_insert(vec![first]);
+    }
+    if max_bits > 1 {
+        for first in DF_BITS + 1..=len {
+            for second in first + 1..=len {
+                table
+                    .entry(error(&[first, second]))
+                    .or_insert(vec![first, second]);
+            }
+        }
+    }
+    table
+}
+
 /// Calculate the reminder of the message
 ///
 /// # Arguments
@@ -138,6 +234,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_syndrome() {
+        let squitters = [
+            ("8D40621D58C382D690C8AC2863A7", 0),
+            ("8D406B902015A678D4D220AA4BDA", 0),
+            ("5D39CF01BF89DD", 0),
+            ("5D3982A87C156D", 0x37),
+        ];
+
+        for (squitter, value) in squitters.iter() {
+            let message = clean_squitter(squitter).expect("Failed to clean squitter");
+            assert_eq!(syndrome(&message), *value, "{}", squitter);
+        }
+    }
+
+    #[test]
+    fn test_fix_errors() {
+        let valid =
+            clean_squitter("8D40621D58C382D690C8AC2863A7").expect("Failed to clean squitter");
+
+        let mut message = valid.clone();
+        assert_eq!(fix_errors(&mut message, 1), Some(0));
+
+        flip_bit(&mut message, 40);
+        assert_eq!(fix_errors(&mut message, 0), None);
+        assert_eq!(fix_errors(&mut message, 1), Some(1));
+        assert_eq!(message, valid);
+
+        flip_bit(&mut message, 6);
+        flip_bit(&mut message, 112);
+        assert_eq!(fix_errors(&mut message, 1), None);
+        assert_eq!(fix_errors(&mut message, 2), Some(2));
+        assert_eq!(message, valid);
+
+        let valid = clean_squitter("5D39CF01BF89DD").expect("Failed to clean squitter");
+        let mut message = valid.clone();
+        flip_bit(&mut message, 30);
+        assert_eq!(fix_errors(&mut message, 1), Some(1));
+        assert_eq!(message, valid);
+    }
+
     #[test]
     fn test_reminder() {
         let squitters = [
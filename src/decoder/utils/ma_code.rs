@@ -12,6 +12,11 @@ use log::debug;
 /// * `message` - The ADS-B message as a slice of `u32` values.
 ///
 pub(crate) fn ma_code(message: &[u32]) -> Option<u16> {
+    ma_code_at(message, 0)
+}
+
+/// Calculates the MA code of a 13-bit identity field located `offset` nibbles after bit 20.
+pub(crate) fn ma_code_at(message: &[u32], offset: usize) -> Option<u16> {
     let mut result = 0u16;
 
     let bit_positions = [
@@ -32,7 +37,7 @@ pub(crate) fn ma_code(message: &[u32]) -> Option<u16> {
     ];
 
     for (i, (byte_index, bit_index)) in bit_positions.iter().enumerate() {
-        result |= (((message[*byte_index] >> bit_index) & 1) as u16) << (13 - i);
+        result |= (((message.get(byte_index + offset)? >> bit_index) & 1) as u16) << (13 - i);
     }
 
     debug!("MA code: {:016b}", result);
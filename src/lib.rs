@@ -4,16 +4,19 @@ mod decoder;
 mod errors;
 mod logger;
 mod reader;
+mod server;
 
 pub use arguments::Args;
 pub use decoder::{
-    DF, DisplayFlags, Downlink, Legend, LegendHeaders, Plane, Planes, UpdateFromDownlink,
-    get_downlink_format, get_icao, get_message, set_observer_coords_from_str,
+    BdsStatistics, DF, DisplayFlags, Downlink, Frame, Legend, LegendHeaders, Plane, Planes,
+    UpdateFromDownlink, bds_statistics, get_downlink_format, get_icao, get_message,
+    get_message_from_bytes, set_error_correction, set_icao_filter, set_observer_coords_from_str,
+    set_position_margin,
 };
 pub use errors::AppResult;
 pub use logger::initialize_logger;
-pub use reader::spawn_reader_thread;
+pub use reader::{InputFormat, spawn_reader_thread};
 pub(crate) use {
     counters::AppCounters,
-    decoder::{flag_and_range_value, range_value},
+    decoder::{flag_and_range_value, get_hex_message, range_value},
 };
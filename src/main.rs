@@ -1,5 +1,6 @@
 use squitterator::{
-    AppResult, Args, Planes, initialize_logger, set_observer_coords_from_str, spawn_reader_thread,
+    AppResult, Args, Planes, initialize_logger, set_error_correction, set_icao_filter,
+    set_observer_coords_from_str, set_position_margin, spawn_reader_thread,
 };
 
 use clap::Parser;
@@ -34,6 +35,10 @@ fn main() -> AppResult<()> {
         set_observer_coords_from_str(coord_str);
     }
 
+    set_error_correction(args.fix as u32);
+    set_icao_filter(args.icao_ttl);
+    set_position_margin(args.position_margin);
+
     let planes = Planes::new();
     let reader_thread = spawn_reader_thread(args, planes);
 
@@ -1,78 +1,264 @@
+mod beast;
+mod clock;
+mod format;
+mod replay;
+
+pub use format::InputFormat;
+
 use crate::{
-    AppCounters, Args, DF, DisplayFlags, Downlink, Legend, LegendHeaders, Planes,
-    get_downlink_format, get_icao, get_message,
+    AppCounters, Args, DF, DisplayFlags, Frame, Legend, LegendHeaders, Planes, bds_statistics,
+    decoder::{address_type, get_capability, remember_icao},
+    get_downlink_format, get_hex_message, get_icao,
+    server::{Broadcaster, sbs_message, serve_aircraft_json},
 };
+use beast::{BeastFrame, BeastReader};
+use chrono::{DateTime, Utc};
+use clock::{Clock, FrameClock, WallClock};
 use log::{debug, error, info};
+use replay::{Pace, Replay};
 use std::{
     fs::File,
     io::{BufRead, BufReader, Result},
     net::TcpStream,
+    ops::ControlFlow,
     sync::{Arc, Mutex},
     thread::{self, sleep},
     time::Duration,
 };
 
-fn read_lines<R: BufRead>(reader: R, args: &Args, planes: &mut Planes) -> Result<()> {
-    let downlink_error_log_file = args
-        .downlink_log
-        .as_ref()
-        .map(|f| Mutex::new(File::create(f).expect("Unable to create downlink log file")));
+struct ReaderState {
+    downlink_log: Option<Mutex<File>>,
+    display_flags: DisplayFlags,
+    headers: LegendHeaders,
+    app_state: AppCounters,
+    clock: Box<dyn Clock>,
+    replay: Option<Replay>,
+    sbs: Option<Broadcaster>,
+}
+
+impl ReaderState {
+    fn from_args(args: &Args, sbs: Option<&Broadcaster>) -> Self {
+        let downlink_log = args
+            .downlink_log
+            .as_ref()
+            .map(|f| Mutex::new(File::create(f).expect("Unable to create downlink log file")));
+
+        let display_flags = DisplayFlags::from_arg_str(&args.display_info.concat());
 
-    let display_flags = DisplayFlags::from_arg_str(&args.display_info.concat());
+        if !display_flags.quiet() {
+            display_legend(&display_flags);
+        }
+
+        let headers = LegendHeaders::from_display_flags(&display_flags);
+
+        // Live feeds are stamped on arrival, recordings with their own timing
+        let clock: Box<dyn Clock> = if args.tcp.is_empty() {
+            Box::new(FrameClock::new())
+        } else {
+            Box::new(WallClock)
+        };
 
-    if !display_flags.quiet() {
-        display_legend(&display_flags);
+        let replay = args.is_replay().then(|| {
+            Replay::with_stdin_controls(
+                args.speed,
+                args.start_at,
+                args.end_at,
+                chrono::Duration::seconds(args.update),
+            )
+        });
+
+        ReaderState {
+            downlink_log,
+            display_flags,
+            headers,
+            app_state: AppCounters::from_update_interval(args.update),
+            clock,
+            replay,
+            sbs: sbs.cloned(),
+        }
     }
+}
 
-    let headers = LegendHeaders::from_display_flags(&display_flags);
+fn read_input<R: BufRead>(
+    mut reader: R,
+    args: &Args,
+    planes: &mut Planes,
+    sbs: Option<&Broadcaster>,
+) -> Result<()> {
+    let format = match args.format {
+        InputFormat::Auto => InputFormat::detect(reader.fill_buf()?),
+        format => format,
+    };
+    info!("Input format: {}", format);
 
-    let mut app_state = AppCounters::from_update_interval(args.update);
+    match format {
+        InputFormat::Beast => read_beast(reader, args, planes, sbs),
+        _ => read_lines(reader, format, args, planes, sbs),
+    }
+}
+
+fn read_lines<R: BufRead>(
+    reader: R,
+    format: InputFormat,
+    args: &Args,
+    planes: &mut Planes,
+    sbs: Option<&Broadcaster>,
+) -> Result<()> {
+    let mut state = ReaderState::from_args(args, sbs);
 
-    for line in reader.lines().map_while(Result::ok) {
-        let Some(message) = get_message(&line) else {
+    for bytes in reader.split(b'\n').map_while(Result::ok) {
+        let line = String::from_utf8_lossy(&bytes);
+        let line = line.trim();
+        if line.is_empty() {
             continue;
-        };
+        }
 
-        let Some(df) = get_downlink_format(&message) else {
+        let Some(frame) = format.parse_line(line) else {
+            debug!("Invalid {} squitter: {}", format, line);
+            state.app_state.update_invalid_count(format);
             continue;
         };
-
-        debug!("DF:{}, L:{}", df, &line);
-
-        let Some(icao) = get_icao(&message, df) else {
+        let Some(mut frame) = frame.validated() else {
+            debug!("Rejected squitter: {}", line);
+            state.app_state.increment_rejected_count();
             continue;
         };
 
-        if let Some(m) = &args.log_messages
-            && m.contains(&df)
-        {
-            error!("DF:{}, L:{}", df, line);
+        if process_frame(&mut frame, line, args, planes, &mut state)?.is_break() {
+            break;
         }
+    }
+    Ok(())
+}
 
-        if let Some(only) = &args.filter
-            && only.iter().all(|&x| x != df)
-        {
+fn read_beast<R: BufRead>(
+    reader: R,
+    args: &Args,
+    planes: &mut Planes,
+    sbs: Option<&Broadcaster>,
+) -> Result<()> {
+    let mut state = ReaderState::from_args(args, sbs);
+
+    for beast_frame in BeastReader::new(reader).filter(BeastFrame::is_mode_s) {
+        let Some(mut frame) = beast_frame.frame().validated() else {
+            state.app_state.increment_rejected_count();
             continue;
-        }
+        };
 
-        if args.count_df {
-            app_state.update_count(df);
+        let line = get_hex_message(&frame.message);
+        if process_frame(&mut frame, &line, args, planes, &mut state)?.is_break() {
+            break;
         }
+    }
+    Ok(())
+}
 
-        let now = chrono::Utc::now();
-        if let Ok(downlink) = DF::from_message(&message) {
-            if let Some(ref downlink_error_log_file) = downlink_error_log_file {
-                downlink.log(downlink_error_log_file)?;
-            }
-            planes.update_aircraft(&downlink, &message, df, icao, args);
-            planes.cleanup(&mut app_state, now, args.delete_after);
+fn process_frame(
+    frame: &mut Frame,
+    line: &str,
+    args: &Args,
+    planes: &mut Planes,
+    state: &mut ReaderState,
+) -> Result<ControlFlow<()>> {
+    if frame.repaired_bits > 0 {
+        state.app_state.update_repaired_count(frame.repaired_bits);
+    }
+
+    let message = &frame.message;
+    let Some(df) = get_downlink_format(message) else {
+        return Ok(ControlFlow::Continue(()));
+    };
+
+    debug!("DF:{}, L:{}", df, line);
+
+    let Some(icao) = get_icao(message, df) else {
+        return Ok(ControlFlow::Continue(()));
+    };
+
+    if let Some(m) = &args.log_messages
+        && m.contains(&df)
+    {
+        error!("DF:{}, L:{}", df, line);
+    }
+
+    // Frames of untimed recordings are stamped on arrival, but keep their time unknown
+    frame.received = state.clock.time(frame);
+    let now = frame.received.unwrap_or_else(Utc::now);
+    // Addresses are learnt before the DF filter, DF18 with CF other than 0 carries non-ICAO ones
+    if matches!(df, 11 | 17) || (df == 18 && get_capability(message) == 0) {
+        remember_icao(icao, now);
+    }
+
+    if let Some(only) = &args.filter
+        && only.iter().all(|&x| x != df)
+    {
+        return Ok(ControlFlow::Continue(()));
+    }
+
+    if args.count_df {
+        state.app_state.update_count(df);
+    }
+
+    if pace_replay(args, planes, state, now).is_break() {
+        return Ok(ControlFlow::Break(()));
+    }
+
+    // Comm-B replies are inferred against the same state as the aircraft they update
+    let key = address_type(message, df).key(icao);
+    let (known, capability) = match df {
+        20 | 21 => planes.comm_b_context(key, args.relaxed),
+        _ => Default::default(),
+    };
+    if let Ok(downlink) = DF::from_message_with_state(message, &known, capability.as_ref()) {
+        if let Some(ref downlink_log) = state.downlink_log {
+            downlink.log(downlink_log, frame)?;
+        }
+        planes.update_aircraft(&downlink, frame, df, icao, now, args);
+        if let Some(sbs) = &state.sbs {
+            broadcast_sbs(sbs, planes, &downlink, key, now, args.min_nic);
         }
+        planes.cleanup(&mut state.app_state, now, args.delete_after);
+    }
+
+    if !state.display_flags.quiet() && state.app_state.is_time_to_refresh(&Utc::now(), args.update)
+    {
+        display_planes(args, planes, state, now);
+    }
+    Ok(ControlFlow::Continue(()))
+}
 
-        if !display_flags.quiet() && app_state.is_time_to_refresh(&now, args.update) {
-            display_planes(args, planes, &display_flags, &headers, &mut app_state, now);
+/// Holds the frame back until it is due in the replay, refreshing the display while paused.
+fn pace_replay(
+    args: &Args,
+    planes: &mut Planes,
+    state: &mut ReaderState,
+    now: DateTime<Utc>,
+) -> ControlFlow<()> {
+    while let Some(replay) = state.replay.as_mut() {
+        match replay.pace(now) {
+            Pace::Play => break,
+            Pace::End => return ControlFlow::Break(()),
+            Pace::Hold if !state.display_flags.quiet() => display_planes(args, planes, state, now),
+            Pace::Hold => {}
         }
     }
-    Ok(())
+    ControlFlow::Continue(())
+}
+
+fn broadcast_sbs(
+    sbs: &Broadcaster,
+    planes: &Planes,
+    downlink: &DF,
+    key: u32,
+    now: DateTime<Utc>,
+    min_nic: u32,
+) {
+    if let Ok(aircrafts) = planes.aircrafts.read()
+        && let Some(plane) = aircrafts.get(&key)
+        && let Some(line) = sbs_message(downlink, plane, now, min_nic)
+    {
+        sbs.send(line);
+    }
 }
 
 fn display_legend(display_flags: &DisplayFlags) {
@@ -82,47 +268,62 @@ fn display_legend(display_flags: &DisplayFlags) {
     legend.print_legend();
 }
 
-fn display_planes(
-    args: &Args,
-    planes: &mut Planes,
-    display_flags: &DisplayFlags,
-    headers: &LegendHeaders,
-    app_state: &mut AppCounters,
-    now: chrono::DateTime<chrono::Utc>,
-) {
+fn display_planes(args: &Args, planes: &mut Planes, state: &mut ReaderState, now: DateTime<Utc>) {
     clear_screen();
 
-    headers.print_header();
-    headers.print_separator();
+    state.headers.print_header();
+    state.headers.print_separator();
 
-    planes.print(args, display_flags);
+    planes.print(args, &state.display_flags, now);
 
-    headers.print_separator();
+    state.headers.print_separator();
 
     if args.count_df {
-        app_state.print_df_count_line();
+        state.app_state.print_df_count_line();
+        let statistics = bds_statistics();
+        if statistics.replies() > 0 {
+            println!("{}", statistics);
+        }
     }
 
-    app_state.reset_timestamp(now);
+    state.app_state.print_invalid_count_line();
+    state.app_state.print_rejected_count_line();
+    state.app_state.print_repaired_count_line();
+
+    if let Some(replay) = &state.replay {
+        println!("{}", replay.status_line());
+    }
+
+    state.app_state.reset_timestamp(Utc::now());
 }
 
 pub fn spawn_reader_thread(args: Arc<Args>, mut planes: Planes) -> thread::JoinHandle<Result<()>> {
     thread::spawn(move || {
+        // Servers outlive reconnections of the input
+        let sbs = args.sbs_port.map(Broadcaster::bind).transpose()?;
+        if let Some(port) = args.http_port {
+            serve_aircraft_json(port, planes.clone(), args.min_nic)?;
+        }
+
         if !args.tcp.is_empty() {
-            connect_and_read_tcp(args, &mut planes)
+            connect_and_read_tcp(args, &mut planes, sbs.as_ref())
         } else {
-            read_from_file(args, &mut planes)
+            read_from_file(args, &mut planes, sbs.as_ref())
         }
     })
 }
 
-fn connect_and_read_tcp(args: Arc<Args>, planes: &mut Planes) -> Result<()> {
+fn connect_and_read_tcp(
+    args: Arc<Args>,
+    planes: &mut Planes,
+    sbs: Option<&Broadcaster>,
+) -> Result<()> {
     loop {
         match TcpStream::connect(&args.tcp) {
             Ok(stream) => {
                 info!("Successfully connected to the server {}", &args.tcp);
                 let reader = BufReader::new(stream);
-                if let Err(e) = read_lines(reader, &args, planes) {
+                if let Err(e) = read_input(reader, &args, planes, sbs) {
                     error!("Error during reading: {}", e);
                     sleep(Duration::from_secs(5));
                 }
@@ -135,10 +336,10 @@ fn connect_and_read_tcp(args: Arc<Args>, planes: &mut Planes) -> Result<()> {
     }
 }
 
-fn read_from_file(args: Arc<Args>, planes: &mut Planes) -> Result<()> {
+fn read_from_file(args: Arc<Args>, planes: &mut Planes, sbs: Option<&Broadcaster>) -> Result<()> {
     let file = File::open(&args.source)?;
     let reader = BufReader::new(file);
-    read_lines(reader, &args, planes)
+    read_input(reader, &args, planes, sbs)
 }
 
 fn clear_screen() {
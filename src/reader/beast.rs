@@ -0,0 +1,209 @@
+use crate::{Frame, decoder::bytes_to_message};
+use std::{
+    io::{BufRead, Bytes},
+    iter::Peekable,
+};
+
+const ESCAPE: u8 = 0x1A;
+const RECEIVER_ID: u8 = 0xE3;
+
+/// A single frame of the Beast binary protocol.
+///
+/// Every frame starts with `0x1A`, followed by the frame type, a 48-bit MLAT
+/// counter, a signal level byte and the payload. Any `0x1A` inside the frame
+/// is escaped by doubling it.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BeastFrame {
+    pub(crate) kind: u8,
+    pub(crate) timestamp: u64,
+    pub(crate) signal: u8,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) receiver_id: Option<u64>,
+}
+
+impl BeastFrame {
+    /// Returns `true` for Mode S short and long frames.
+    pub(crate) fn is_mode_s(&self) -> bool {
+        matches!(self.kind, b'2' | b'3')
+    }
+
+    /// Converts a Mode S frame into a received [`Frame`], its parity is not checked yet.
+    pub(crate) fn frame(&self) -> Frame {
+        Frame::from_data(
+            bytes_to_message(&self.payload),
+            Some(self.timestamp),
+            Some(self.signal),
+            self.receiver_id,
+        )
+    }
+}
+
+/// Payload length of the given frame type, `None` for unknown types.
+fn payload_len(kind: u8) -> Option<usize> {
+    match kind {
+        b'1' => Some(2),
+        b'2' => Some(7),
+        b'3' => Some(14),
+        _ => None,
+    }
+}
+
+enum Unescaped {
+    Byte(u8),
+    FrameStart,
+}
+
+/// Iterator over the Beast frames of a byte stream.
+///
+/// The iterator resynchronises on the next frame start whenever it meets
+/// an unknown frame type or a frame truncated by an unescaped `0x1A`.
+/// It ends on the end of the stream or on the first read error.
+///
+/// Receiver id frames (type `0xE3`) are not returned, the announced id
+/// is attached to every following frame instead.
+pub(crate) struct BeastReader<R: BufRead> {
+    bytes: Peekable<Bytes<R>>,
+    synced: bool,
+    receiver_id: Option<u64>,
+}
+
+impl<R: BufRead> BeastReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        BeastReader {
+            bytes: reader.bytes().peekable(),
+            synced: false,
+            receiver_id: None,
+        }
+    }
+
+    fn unescaped(&mut self) -> Option<Unescaped> {
+        let byte = self.bytes.next()?.ok()?;
+        if byte != ESCAPE {
+            return Some(Unescaped::Byte(byte));
+        }
+        match self.bytes.peek() {
+            Some(Ok(ESCAPE)) => {
+                self.bytes.next();
+                Some(Unescaped::Byte(ESCAPE))
+            }
+            Some(Ok(_)) => Some(Unescaped::FrameStart),
+            _ => None,
+        }
+    }
+
+    /// Reads `len` unescaped bytes, `Ok(None)` if the frame was cut short by a new frame.
+    fn read_frame_bytes(&mut self, len: usize) -> Option<Option<Vec<u8>>> {
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            match self.unescaped()? {
+                Unescaped::Byte(byte) => bytes.push(byte),
+                Unescaped::FrameStart => {
+                    self.synced = true;
+                    return Some(None);
+                }
+            }
+        }
+        Some(Some(bytes))
+    }
+}
+
+impl<R: BufRead> Iterator for BeastReader<R> {
+    type Item = BeastFrame;
+
+    fn next(&mut self) -> Option<BeastFrame> {
+        loop {
+            if !self.synced && self.bytes.next()?.ok()? != ESCAPE {
+                continue;
+            }
+            self.synced = false;
+
+            let kind = self.bytes.next()?.ok()?;
+            if kind == RECEIVER_ID {
+                if let Some(bytes) = self.read_frame_bytes(8)? {
+                    self.receiver_id = Some(to_u64(&bytes));
+                }
+                continue;
+            }
+
+            let Some(len) = payload_len(kind) else {
+                continue;
+            };
+
+            let Some(bytes) = self.read_frame_bytes(7 + len)? else {
+                continue;
+            };
+
+            return Some(BeastFrame {
+                kind,
+                timestamp: to_u64(&bytes[..6]),
+                signal: bytes[6],
+                payload: bytes[7..].to_vec(),
+                receiver_id: self.receiver_id,
+            });
+        }
+    }
+}
+
+fn to_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG: [u8; 23] = [
+        0x1A, b'3', 0x00, 0x97, 0x36, 0xE2, 0x73, 0x6B, 0x80, 0x8D, 0x40, 0x71, 0x7E, 0xF8, 0x21,
+        0x00, 0x02, 0x00, 0x49, 0xB8, 0xA8, 0x88, 0x7A,
+    ];
+
+    #[test]
+    fn test_long_frame() {
+        let frames = BeastReader::new(&LONG[..]).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].kind, b'3');
+        assert_eq!(frames[0].timestamp, 0x009736E2736B);
+        assert_eq!(frames[0].signal, 0x80);
+        assert_eq!(frames[0].payload, LONG[9..].to_vec());
+        assert!(frames[0].is_mode_s());
+    }
+
+    #[test]
+    fn test_escaped_bytes() {
+        let stream = [
+            0x1A, b'2', 0x00, 0x00, 0x00, 0x00, 0x1A, 0x1A, 0x01, 0x40, 0x02, 0xE1, 0x97, 0xB0,
+            0x01, 0x79, 0xC3,
+        ];
+        let frames = BeastReader::new(&stream[..]).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].timestamp, 0x1A01);
+        assert_eq!(frames[0].signal, 0x40);
+        assert_eq!(
+            frames[0].payload,
+            vec![0x02, 0xE1, 0x97, 0xB0, 0x01, 0x79, 0xC3]
+        );
+    }
+
+    #[test]
+    fn test_resync() {
+        let mut stream = vec![0x55, 0x1A, b'9', 0x00, 0x1A, b'3', 0x00, 0x01];
+        stream.extend_from_slice(&LONG);
+        stream.extend_from_slice(&[0x1A, b'1', 0, 0, 0, 0, 0, 1, 0x10, 0x12, 0x34]);
+        let frames = BeastReader::new(&stream[..]).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].payload, LONG[9..].to_vec());
+        assert_eq!(frames[1].kind, b'1');
+        assert!(!frames[1].is_mode_s());
+    }
+
+    #[test]
+    fn test_receiver_id() {
+        let mut stream = LONG.to_vec();
+        stream.extend_from_slice(&[0x1A, 0xE3, 0, 0, 0, 0, 0x1A, 0x1A, 0xBE, 0xEF, 0x01]);
+        stream.extend_from_slice(&LONG);
+        let frames = BeastReader::new(&stream[..]).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].receiver_id, None);
+        assert_eq!(frames[1].receiver_id, Some(0x1ABEEF01));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Frame;
+use chrono::{DateTime, Duration, Utc};
+
+/// Receiver counter frequency of the MLAT timestamps.
+const COUNTER_HZ: u64 = 12_000_000;
+
+/// Source of the time aircraft state is stamped with.
+pub(crate) trait Clock {
+    /// Returns the reception time of the frame, `None` when it is not known.
+    fn time(&mut self, frame: &Frame) -> Option<DateTime<Utc>>;
+}
+
+/// Stamps every frame with the current wall-clock time, used for live feeds.
+pub(crate) struct WallClock;
+
+impl Clock for WallClock {
+    fn time(&mut self, _frame: &Frame) -> Option<DateTime<Utc>> {
+        Some(Utc::now())
+    }
+}
+
+/// Stamps frames with the time they were received at, used for recordings.
+///
+/// Line timestamps are taken as they are. MLAT counters are anchored to the
+/// wall-clock time of the first counter seen and advance by the counter
+/// difference; the anchor is moved when the counter goes backwards (receiver
+/// restart). Frames without any timing keep the last frame time, their time
+/// is unknown when the recording carries no timing at all.
+pub(crate) struct FrameClock {
+    anchor: Option<(u64, DateTime<Utc>)>,
+    last: Option<DateTime<Utc>>,
+}
+
+impl FrameClock {
+    pub(crate) fn new() -> Self {
+        FrameClock {
+            anchor: None,
+            last: None,
+        }
+    }
+
+    fn counter_time(&mut self, counter: u64) -> DateTime<Utc> {
+        let (anchor_counter, anchor_time) = match self.anchor {
+            Some((anchor_counter, anchor_time)) if counter >= anchor_counter => {
+                (anchor_counter, anchor_time)
+            }
+            _ => {
+                let anchor = (counter, self.last.unwrap_or_else(Utc::now));
+                self.anchor = Some(anchor);
+                anchor
+            }
+        };
+        let ticks = counter - anchor_counter;
+        anchor_time
+            + Duration::seconds((ticks / COUNTER_HZ) as i64)
+            + Duration::nanoseconds(((ticks % COUNTER_HZ) * 1_000_000_000 / COUNTER_HZ) as i64)
+    }
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for FrameClock {
+    fn time(&mut self, frame: &Frame) -> Option<DateTime<Utc>> {
+        let time = match (frame.received, frame.timestamp) {
+            (Some(received), _) => received,
+            (None, Some(counter)) => self.counter_time(counter),
+            (None, None) => return self.last,
+        };
+        self.last = Some(time);
+        Some(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp: Option<u64>) -> Frame {
+        Frame::from_data(Vec::new(), timestamp, None, None)
+    }
+
+    #[test]
+    fn test_counter_time() {
+        let mut clock = FrameClock::new();
+        let start = clock.time(&frame(Some(1_000))).expect("No time");
+        let later = clock
+            .time(&frame(Some(1_000 + 90 * COUNTER_HZ + COUNTER_HZ / 4)))
+            .expect("No time");
+        assert_eq!((later - start).num_milliseconds(), 90_250);
+        assert_eq!(clock.time(&frame(None)), Some(later));
+    }
+
+    #[test]
+    fn test_counter_reset() {
+        let mut clock = FrameClock::new();
+        clock.time(&frame(Some(100 * COUNTER_HZ)));
+        let before_reset = clock.time(&frame(Some(160 * COUNTER_HZ))).expect("No time");
+        let after_reset = clock.time(&frame(Some(COUNTER_HZ)));
+        assert_eq!(after_reset, Some(before_reset));
+        let later = clock.time(&frame(Some(11 * COUNTER_HZ))).expect("No time");
+        assert_eq!((later - before_reset).num_seconds(), 10);
+    }
+
+    #[test]
+    fn test_untimed_frames() {
+        let mut clock = FrameClock::new();
+        assert_eq!(clock.time(&frame(None)), None);
+        assert_eq!(clock.time(&frame(None)), None);
+    }
+
+    #[test]
+    fn test_received_time() {
+        let received = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let mut clock = FrameClock::new();
+        let mut line_frame = frame(Some(5));
+        line_frame.received = Some(received);
+        assert_eq!(clock.time(&line_frame), Some(received));
+        assert_eq!(clock.time(&frame(None)), Some(received));
+    }
+}
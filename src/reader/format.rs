@@ -0,0 +1,226 @@
+use crate::Frame;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use std::fmt::{self, Display};
+
+/// Framing of the input stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum InputFormat {
+    /// Detect the framing from the first bytes of the stream
+    Auto,
+    /// Plain hex squitters, one per line
+    Hex,
+    /// AVR `*<squitter>;`
+    Avr,
+    /// AVR with 48-bit MLAT counter `@<counter><squitter>;`
+    AvrMlat,
+    /// Beast ASCII with MLAT counter and signal level `<<counter><signal><squitter>;`
+    BeastAscii,
+    /// Beast binary (e.g. port 30005)
+    Beast,
+}
+
+impl Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            InputFormat::Auto => "AUTO",
+            InputFormat::Hex => "HEX",
+            InputFormat::Avr => "AVR",
+            InputFormat::AvrMlat => "MLAT",
+            InputFormat::BeastAscii => "BEAST-ASCII",
+            InputFormat::Beast => "BEAST",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl InputFormat {
+    /// Sniffs the input format from the first buffered bytes of the stream.
+    ///
+    /// Beast binary is recognised by a `0x1A` escape followed by a frame type,
+    /// the text formats by the marker of the first non-empty line.
+    /// Falls back to plain hex when nothing is recognised.
+    pub(crate) fn detect(buf: &[u8]) -> Self {
+        if buf
+            .windows(2)
+            .any(|w| w[0] == 0x1A && matches!(w[1], b'1' | b'2' | b'3'))
+        {
+            return InputFormat::Beast;
+        }
+
+        buf.split(|&b| b == b'\n')
+            .map(|line| String::from_utf8_lossy(line))
+            .find_map(|line| {
+                let (_, line) = strip_line_timestamp(line.trim());
+                let line = line.as_bytes();
+                match line.first()? {
+                    b'*' => Some(InputFormat::Avr),
+                    b'@' => Some(InputFormat::AvrMlat),
+                    b'<' => Some(InputFormat::BeastAscii),
+                    _ if line.iter().all(u8::is_ascii_hexdigit) => Some(InputFormat::Hex),
+                    _ => None,
+                }
+            })
+            .unwrap_or(InputFormat::Hex)
+    }
+
+    /// Extracts the squitter and its metadata from a text line framed in this format.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - A trimmed text line, optionally prefixed with a Unix time (e.g. `ts '%.s'` output).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Frame>` - The received frame, `None` if the line is not framed in this format.
+    pub(crate) fn parse_line(&self, line: &str) -> Option<Frame> {
+        let (received, line) = strip_line_timestamp(line);
+        let (payload, prefix) = match self {
+            InputFormat::Hex => (line, 0),
+            InputFormat::Avr => (line.strip_prefix('*')?.strip_suffix(';')?, 0),
+            InputFormat::AvrMlat => (line.strip_prefix('@')?.strip_suffix(';')?, 12),
+            InputFormat::BeastAscii => (line.strip_prefix('<')?.strip_suffix(';')?, 14),
+            InputFormat::Auto | InputFormat::Beast => return None,
+        };
+
+        let message = payload
+            .chars()
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<u32>>>()?;
+
+        if !matches!(message.len().checked_sub(prefix)?, 14 | 28) {
+            return None;
+        }
+
+        let timestamp = (prefix >= 12).then(|| {
+            message[..12]
+                .iter()
+                .fold(0u64, |acc, &x| (acc << 4) | x as u64)
+        });
+        let signal = (prefix == 14).then(|| ((message[12] << 4) | message[13]) as u8);
+
+        let mut frame = Frame::from_data(message[prefix..].to_vec(), timestamp, signal, None);
+        frame.received = received;
+        Some(frame)
+    }
+}
+
+/// Splits a leading Unix time in seconds off the line.
+fn strip_line_timestamp(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    line.split_once(char::is_whitespace)
+        .and_then(|(time, rest)| {
+            let seconds = time.parse::<f64>().ok()?;
+            let received = DateTime::from_timestamp_micros((seconds * 1e6) as i64)?;
+            Some((Some(received), rest.trim_start()))
+        })
+        .unwrap_or((None, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let streams: [(&[u8], InputFormat); 8] = [
+            (b"8D40621D58C382D690C8AC2863A7\n", InputFormat::Hex),
+            (b"\n*8D40621D58C382D690C8AC2863A7;\n", InputFormat::Avr),
+            (
+                b"@009736E2736B8D40717EF82100020049B8A8887A;\n",
+                InputFormat::AvrMlat,
+            ),
+            (
+                b"<009736E2736B808D40717EF82100020049B8A8887A;\n",
+                InputFormat::BeastAscii,
+            ),
+            (b"\x1a3\x00\x97\x36\xe2\x73\x6b\x80\x8d", InputFormat::Beast),
+            (
+                b"Trying 10.0.0.1...\n@05FBF7664BD68DABB775F83300060049B88771C0;\n",
+                InputFormat::AvrMlat,
+            ),
+            (
+                b"1700000000.25 *8D40621D58C382D690C8AC2863A7;\n",
+                InputFormat::Avr,
+            ),
+            (b"", InputFormat::Hex),
+        ];
+        for (stream, format) in streams {
+            assert_eq!(InputFormat::detect(stream), format);
+        }
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let squitter = vec![0, 2, 14, 1, 9, 7, 11, 0, 0, 1, 7, 9, 12, 3];
+        let lines = [
+            (InputFormat::Hex, "02E197B00179C3"),
+            (InputFormat::Avr, "*02E197B00179C3;"),
+            (InputFormat::AvrMlat, "@05FBF77B10E502E197B00179C3;"),
+            (InputFormat::BeastAscii, "<05FBF77B10E5A002E197B00179C3;"),
+        ];
+        for (format, line) in lines {
+            assert_eq!(
+                format.parse_line(line).map(|frame| frame.message),
+                Some(squitter.clone()),
+                "{}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_line_metadata() {
+        let frame = InputFormat::AvrMlat
+            .parse_line("@05FBF77B10E502E197B00179C3;")
+            .expect("Failed to parse line");
+        assert_eq!(
+            (frame.timestamp, frame.signal),
+            (Some(0x05FBF77B10E5), None)
+        );
+
+        let frame = InputFormat::BeastAscii
+            .parse_line("<05FBF77B10E5A002E197B00179C3;")
+            .expect("Failed to parse line");
+        assert_eq!(
+            (frame.timestamp, frame.signal),
+            (Some(0x05FBF77B10E5), Some(0xA0))
+        );
+
+        let frame = InputFormat::Avr
+            .parse_line("*02E197B00179C3;")
+            .expect("Failed to parse line");
+        assert_eq!((frame.timestamp, frame.signal), (None, None));
+        assert_eq!(frame.received, None);
+    }
+
+    #[test]
+    fn test_parse_line_received() {
+        let frame = InputFormat::Avr
+            .parse_line("1700000000.250 *02E197B00179C3;")
+            .expect("Failed to parse line");
+        assert_eq!(
+            frame.received,
+            DateTime::from_timestamp_millis(1_700_000_000_250)
+        );
+        assert_eq!(
+            frame.message,
+            vec![0, 2, 14, 1, 9, 7, 11, 0, 0, 1, 7, 9, 12, 3]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_invalid() {
+        let lines = [
+            (InputFormat::Hex, "*02E197B00179C3;"),
+            (InputFormat::Hex, "02E197B00179C"),
+            (InputFormat::Avr, "02E197B00179C3"),
+            (InputFormat::Avr, "*02E197B00179G3;"),
+            (InputFormat::AvrMlat, "*02E197B00179C3;"),
+            (InputFormat::AvrMlat, "@02E197B00179C3;"),
+            (InputFormat::BeastAscii, "@05FBF77B10E502E197B00179C3;"),
+        ];
+        for (format, line) in lines {
+            assert_eq!(format.parse_line(line), None, "{} {}", format, line);
+        }
+    }
+}
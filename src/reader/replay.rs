@@ -0,0 +1,280 @@
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use std::{
+    io::BufRead,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+/// Commands typed on stdin while a recording is replayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ReplayControl {
+    /// `p` or an empty line
+    TogglePause,
+    /// `s`, advances one update interval and pauses again
+    Step,
+    /// `+`
+    Faster,
+    /// `-`
+    Slower,
+    /// `q`
+    Quit,
+}
+
+impl ReplayControl {
+    fn from_line(line: &str) -> Option<Self> {
+        match line.trim() {
+            "" | "p" => Some(ReplayControl::TogglePause),
+            "s" | "n" => Some(ReplayControl::Step),
+            "+" => Some(ReplayControl::Faster),
+            "-" => Some(ReplayControl::Slower),
+            "q" => Some(ReplayControl::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// What the reader does with the frame it is about to process.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Pace {
+    /// Process the frame
+    Play,
+    /// The replay has just been paused, refresh the display before asking again
+    Hold,
+    /// The end of the replay window has been reached
+    End,
+}
+
+/// Paces the frames of a recording by their time.
+///
+/// Frames before `start_at` are processed without delay so that the aircraft
+/// state is built up, frames after `end_at` end the replay. Offsets are
+/// relative to the time of the first frame.
+pub(crate) struct Replay {
+    speed: Option<f64>,
+    start_at: Duration,
+    end_at: Option<Duration>,
+    step: Duration,
+    first: Option<DateTime<Utc>>,
+    origin: Option<(DateTime<Utc>, Instant)>,
+    position: Duration,
+    paused: bool,
+    holding: bool,
+    step_until: Option<DateTime<Utc>>,
+    quit: bool,
+    controls: Receiver<ReplayControl>,
+}
+
+impl Replay {
+    pub(crate) fn new(
+        speed: Option<f64>,
+        start_at: Option<Duration>,
+        end_at: Option<Duration>,
+        step: Duration,
+        controls: Receiver<ReplayControl>,
+    ) -> Self {
+        Replay {
+            speed,
+            start_at: start_at.unwrap_or_else(Duration::zero),
+            end_at,
+            step,
+            first: None,
+            origin: None,
+            position: Duration::zero(),
+            paused: false,
+            holding: false,
+            step_until: None,
+            quit: false,
+            controls,
+        }
+    }
+
+    /// Creates a replay controlled by commands typed on stdin.
+    pub(crate) fn with_stdin_controls(
+        speed: Option<f64>,
+        start_at: Option<Duration>,
+        end_at: Option<Duration>,
+        step: Duration,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+                if let Some(control) = ReplayControl::from_line(&line)
+                    && sender.send(control).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Replay::new(speed, start_at, end_at, step, receiver)
+    }
+
+    fn apply(&mut self, control: ReplayControl, now: DateTime<Utc>) {
+        debug!("Replay control: {:?}", control);
+        match control {
+            ReplayControl::TogglePause => {
+                self.paused = !self.paused;
+                self.step_until = None;
+            }
+            ReplayControl::Step => {
+                self.paused = false;
+                self.step_until = Some(now + self.step);
+            }
+            ReplayControl::Faster => self.speed = self.speed.map(|speed| speed * 2.0),
+            ReplayControl::Slower => self.speed = self.speed.map(|speed| speed / 2.0),
+            ReplayControl::Quit => self.quit = true,
+        }
+        self.origin = None;
+    }
+
+    /// Waits until the frame stamped `now` is due.
+    pub(crate) fn pace(&mut self, now: DateTime<Utc>) -> Pace {
+        loop {
+            match self.controls.try_recv() {
+                Ok(control) => self.apply(control, now),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // No one is left to resume the replay
+                    self.paused = false;
+                    break;
+                }
+            }
+        }
+
+        let first = *self.first.get_or_insert(now);
+        self.position = now - first;
+
+        if self.quit || self.end_at.is_some_and(|end_at| self.position > end_at) {
+            return Pace::End;
+        }
+
+        if self.position < self.start_at {
+            return Pace::Play;
+        }
+
+        if self.step_until.is_some_and(|step_until| now >= step_until) {
+            self.step_until = None;
+            self.paused = true;
+        }
+
+        if self.paused {
+            if !self.holding {
+                self.holding = true;
+                return Pace::Hold;
+            }
+            match self.controls.recv() {
+                Ok(control) => self.apply(control, now),
+                Err(_) => self.paused = false,
+            }
+            return self.pace(now);
+        }
+        self.holding = false;
+
+        if let Some(speed) = self.speed
+            && self.step_until.is_none()
+        {
+            let (origin_time, origin_instant) = *self.origin.get_or_insert((now, Instant::now()));
+            let offset = (now - origin_time).to_std().unwrap_or_default();
+            let due = origin_instant + offset.div_f64(speed);
+            if let Some(delay) = due.checked_duration_since(Instant::now()) {
+                thread::sleep(delay);
+            }
+        }
+
+        Pace::Play
+    }
+
+    /// Formats the replay position, speed and state for the display.
+    pub(crate) fn status_line(&self) -> String {
+        let seconds = self.position.num_seconds();
+        format!(
+            "Replay {:02}:{:02}:{:02} {} {}",
+            seconds / 3600,
+            (seconds / 60) % 60,
+            seconds % 60,
+            match self.speed {
+                Some(speed) => format!("{}x", speed),
+                None => "max".to_string(),
+            },
+            if self.position < self.start_at {
+                "seeking"
+            } else if self.paused {
+                "paused  [Enter] resume, [s] step, [q] quit"
+            } else {
+                "playing [Enter] pause, [+/-] speed, [q] quit"
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).expect("Invalid timestamp")
+    }
+
+    #[test]
+    fn test_window() {
+        let (_sender, receiver) = mpsc::channel();
+        let mut replay = Replay::new(
+            None,
+            Some(Duration::seconds(10)),
+            Some(Duration::seconds(20)),
+            Duration::seconds(3),
+            receiver,
+        );
+        assert_eq!(replay.pace(at(0)), Pace::Play);
+        assert!(replay.status_line().contains("seeking"));
+        assert_eq!(replay.pace(at(15)), Pace::Play);
+        assert!(
+            replay
+                .status_line()
+                .starts_with("Replay 00:00:15 max playing")
+        );
+        assert_eq!(replay.pace(at(21)), Pace::End);
+    }
+
+    #[test]
+    fn test_pause_and_step() {
+        let (sender, receiver) = mpsc::channel();
+        let mut replay = Replay::new(None, None, None, Duration::seconds(3), receiver);
+        assert_eq!(replay.pace(at(0)), Pace::Play);
+
+        sender
+            .send(ReplayControl::TogglePause)
+            .expect("Send failed");
+        assert_eq!(replay.pace(at(1)), Pace::Hold);
+
+        sender.send(ReplayControl::Step).expect("Send failed");
+        assert_eq!(replay.pace(at(1)), Pace::Play);
+        assert_eq!(replay.pace(at(2)), Pace::Play);
+        assert_eq!(replay.pace(at(4)), Pace::Hold);
+
+        sender.send(ReplayControl::Quit).expect("Send failed");
+        assert_eq!(replay.pace(at(4)), Pace::End);
+    }
+
+    #[test]
+    fn test_disconnected_controls_resume() {
+        let (sender, receiver) = mpsc::channel();
+        let mut replay = Replay::new(None, None, None, Duration::seconds(3), receiver);
+        sender
+            .send(ReplayControl::TogglePause)
+            .expect("Send failed");
+        drop(sender);
+        assert_eq!(replay.pace(at(0)), Pace::Play);
+    }
+
+    #[test]
+    fn test_control_from_line() {
+        assert_eq!(
+            ReplayControl::from_line(""),
+            Some(ReplayControl::TogglePause)
+        );
+        assert_eq!(ReplayControl::from_line("s\n"), Some(ReplayControl::Step));
+        assert_eq!(ReplayControl::from_line("x"), None);
+    }
+}
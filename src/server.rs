@@ -0,0 +1,78 @@
+mod aircraft_json;
+mod encounters_json;
+mod http;
+mod sbs;
+
+pub(crate) use http::serve_aircraft_json;
+pub(crate) use sbs::sbs_message;
+
+use aircraft_json::aircraft_json;
+use encounters_json::encounters_json;
+
+use log::{debug, info, warn};
+use std::{
+    io::{Result, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+/// Streams text lines to every client connected to a TCP port.
+///
+/// Lines are queued on a channel and written by a dedicated thread, so the
+/// reader never waits on the network. A client that fails or stalls on a
+/// write for longer than the write timeout is disconnected.
+#[derive(Clone)]
+pub(crate) struct Broadcaster {
+    sender: Sender<String>,
+}
+
+impl Broadcaster {
+    pub(crate) fn bind(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        info!("Listening on port {}", port);
+
+        let clients: Arc<Mutex<Vec<TcpStream>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let accepted = Arc::clone(&clients);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        info!("Client {:?} connected to port {}", stream.peer_addr(), port);
+                        if let Err(e) = stream.set_write_timeout(Some(Duration::from_secs(5))) {
+                            warn!("Unable to set write timeout: {}", e);
+                        }
+                        accepted.lock().expect("Clients lock poisoned").push(stream);
+                    }
+                    Err(e) => warn!("Failed to accept a client on port {}: {}", port, e),
+                }
+            }
+        });
+
+        let (sender, receiver) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for line in receiver {
+                let mut clients = clients.lock().expect("Clients lock poisoned");
+                clients.retain_mut(|client| match client.write_all(line.as_bytes()) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        debug!("Client {:?} disconnected: {}", client.peer_addr(), e);
+                        false
+                    }
+                });
+            }
+        });
+
+        Ok(Broadcaster { sender })
+    }
+
+    pub(crate) fn send(&self, line: String) {
+        // The writer thread only stops when the process exits
+        let _ = self.sender.send(line);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::decoder::Plane;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Formats the aircraft state as a dump1090/readsb `aircraft.json` document.
+///
+/// # Arguments
+///
+/// * `aircrafts` - The tracked aircraft.
+/// * `now` - The current time of the aircraft state, `seen` and `seen_pos` are relative to it.
+/// * `min_nic` - Positions with a lower integrity are left out.
+pub(crate) fn aircraft_json(
+    aircrafts: &HashMap<u32, Plane>,
+    now: DateTime<Utc>,
+    min_nic: u32,
+) -> String {
+    let mut planes = aircrafts.values().collect::<Vec<_>>();
+    planes.sort_by_key(|plane| plane.icao);
+
+    format!(
+        "{{\"now\":{:.1},\"messages\":{},\"aircraft\":[{}]}}",
+        now.timestamp_millis() as f64 / 1000.0,
+        planes
+            .iter()
+            .map(|plane| plane.messages as u64)
+            .sum::<u64>(),
+        planes
+            .iter()
+            .map(|plane| plane_json(plane, now, min_nic))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+fn plane_json(plane: &Plane, now: DateTime<Utc>, min_nic: u32) -> String {
+    let mut fields = vec![
+        format!(
+            "\"hex\":\"{}{:06x}\"",
+            if plane.address_type.is_icao() {
+                ""
+            } else {
+                "~"
+            },
+            plane.icao
+        ),
+        format!("\"type\":\"{}\"", plane.address_type),
+    ];
+    let mut field = |name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            fields.push(format!("\"{}\":{}", name, value));
+        }
+    };
+
+    field("flight", plane.ais.as_deref().map(quoted));
+    field(
+        "r",
+        plane
+            .registration
+            .as_ref()
+            .and_then(|r| r.aircraft.as_deref())
+            .map(quoted),
+    );
+    field(
+        "t",
+        plane.aircraft_type.as_ref().map(|t| quoted(&t.designator)),
+    );
+    field(
+        "alt_baro",
+        match plane.on_ground {
+            Some(true) => Some(quoted("ground")),
+            _ => plane.altitude.map(|v| v.to_string()),
+        },
+    );
+    field("alt_geom", plane.altitude_gnss.map(|v| v.to_string()));
+    field(
+        "gs",
+        plane
+            .ground_movement
+            .map(|v| format!("{:.1}", v))
+            .or(plane.grspeed.map(|v| v.to_string())),
+    );
+    field("track", plane.track.map(|v| v.to_string()));
+    field("baro_rate", plane.vrate.map(|v| v.to_string()));
+    field("squawk", plane.squawk.map(|v| format!("\"{:04}\"", v)));
+    field("category", category(plane.category).map(|v| quoted(&v)));
+    field(
+        "nav_qnh",
+        plane.barometric_pressure_setting.map(|v| v.to_string()),
+    );
+    field(
+        "nav_altitude_mcp",
+        plane.selected_altitude.map(|v| v.to_string()),
+    );
+    let (next_waypoint, waypoint_position, waypoint_bearing) = plane.route_intent(now);
+    field("nav_waypoint", next_waypoint.map(quoted));
+    if let Some(position) = waypoint_position {
+        field("nav_waypoint_lat", Some(format!("{:.6}", position.lat)));
+        field("nav_waypoint_lon", Some(format!("{:.6}", position.lon)));
+        field(
+            "nav_waypoint_altitude",
+            position.crossing_altitude.map(|v| v.to_string()),
+        );
+    }
+    if let Some(bearing) = waypoint_bearing {
+        field(
+            "nav_waypoint_bearing",
+            bearing.bearing.map(|v| format!("{:.1}", v)),
+        );
+        field(
+            "nav_waypoint_time",
+            bearing.time_to_go.map(|v| format!("{:.1}", v)),
+        );
+        field(
+            "nav_waypoint_distance",
+            bearing.distance.map(|v| format!("{:.1}", v)),
+        );
+    }
+    field("nav_heading", plane.selected_heading.map(|v| v.to_string()));
+    field("ias", plane.indicated_airspeed.map(|v| v.to_string()));
+    field("tas", plane.true_airspeed.map(|v| v.to_string()));
+    field("mach", plane.mach_number.map(|v| format!("{:.3}", v)));
+    field("roll", plane.roll_angle.map(|v| v.to_string()));
+    field("mag_heading", plane.heading.map(|v| v.to_string()));
+    field("oat", plane.temperature.map(|v| format!("{:.1}", v)));
+    field("ws", plane.wind.map(|(speed, _)| speed.to_string()));
+    field("wd", plane.wind.map(|(_, direction)| direction.to_string()));
+    if let Some(integrity) = &plane.position_integrity {
+        field("nic", Some(integrity.nic.to_string()));
+        field("rc", integrity.rc.map(|v| format!("{:.0}", v)));
+    }
+    if let Some(position_timestamp) = plane.position_timestamp
+        && plane.position_confirmed
+        && plane.has_position_integrity(min_nic)
+    {
+        field("lat", Some(format!("{:.6}", plane.lat)));
+        field("lon", Some(format!("{:.6}", plane.lon)));
+        field("seen_pos", Some(seconds_since(position_timestamp, now)));
+    }
+    field("version", plane.adsb_version.map(|v| v.to_string()));
+    if let Some(status) = &plane.operational_status {
+        field(
+            "nic_a",
+            status.nic_supplement_a.map(|v| u32::from(v).to_string()),
+        );
+        field("nac_p", status.nac_p.map(|v| v.to_string()));
+        field("sil", status.sil.map(|v| v.to_string()));
+        field(
+            "sil_type",
+            status
+                .sil_supplement
+                .map(|v| quoted(if v { "persample" } else { "perhour" })),
+        );
+        field("gva", status.gva.map(|v| v.to_string()));
+        field(
+            "nic_baro",
+            status.nic_baro.map(|v| u32::from(v).to_string()),
+        );
+        field("sda", status.sda.map(|v| v.to_string()));
+    }
+    field("messages", Some(plane.messages.to_string()));
+    field("seen", Some(seconds_since(plane.timestamp, now)));
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Emitter category letter and number, e.g. `A3`, from the type code and category of TC 1-4.
+fn category((type_code, category): (u32, u32)) -> Option<String> {
+    let set = match type_code {
+        4 => 'A',
+        3 => 'B',
+        2 => 'C',
+        1 => 'D',
+        _ => return None,
+    };
+    Some(format!("{}{}", set, category))
+}
+
+pub(super) fn seconds_since(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    format!(
+        "{:.1}",
+        (now - time).num_milliseconds().max(0) as f64 / 1000.0
+    )
+}
+
+pub(super) fn quoted(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{DF, Downlink, Frame, get_message};
+    use chrono::Duration;
+
+    #[test]
+    fn test_aircraft_json() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let message = get_message("8D4840D6202CC371C32CE0576098").expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        let mut plane = Plane::from_downlink(&downlink, 0x4840D6, now);
+        plane.update_reception(&Frame::new(message), now);
+        plane.squawk = Some(1200);
+
+        let mut aircrafts = HashMap::from([(plane.icao, plane)]);
+        assert_eq!(
+            aircraft_json(&aircrafts, now + Duration::milliseconds(2500), 0),
+            "{\"now\":1700000002.5,\"messages\":1,\"aircraft\":[{\"hex\":\"4840d6\",\"type\":\"adsb_icao\",\"flight\":\"KLM1023\",\"squawk\":\"1200\",\"category\":\"A0\",\"messages\":1,\"seen\":2.5}]}"
+        );
+
+        // A position relative to the observer alone is not exported until confirmed
+        let plane = aircrafts.get_mut(&0x4840D6).expect("Missing plane");
+        plane.position_timestamp = Some(now);
+        (plane.lat, plane.lon) = (52.2572, 3.91937);
+        assert!(!aircraft_json(&aircrafts, now, 0).contains("\"lat\""));
+        aircrafts
+            .get_mut(&0x4840D6)
+            .expect("Missing plane")
+            .position_confirmed = true;
+        assert!(aircraft_json(&aircrafts, now, 0).contains("\"lat\":52.257200,\"lon\":3.919370"));
+    }
+
+    #[test]
+    fn test_next_waypoint() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let mut plane = Plane::new();
+        plane.icao = 0x4840D6;
+        plane.next_waypoint = Some("LOGAN".to_string());
+        plane.waypoint_timestamps[0] = Some(now);
+
+        let aircrafts = HashMap::from([(plane.icao, plane)]);
+        assert!(aircraft_json(&aircrafts, now, 0).contains("\"nav_waypoint\":\"LOGAN\""));
+        assert!(
+            !aircraft_json(&aircrafts, now + Duration::seconds(60), 0).contains("nav_waypoint")
+        );
+    }
+
+    #[test]
+    fn test_category() {
+        assert_eq!(category((4, 3)), Some("A3".to_string()));
+        assert_eq!(category((1, 0)), Some("D0".to_string()));
+        assert_eq!(category((0, 0)), None);
+    }
+}
@@ -0,0 +1,78 @@
+use super::aircraft_json::{quoted, seconds_since};
+use crate::decoder::Encounter;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+/// Formats the recorded ACAS resolution advisories as an `encounters.json` document, oldest first.
+///
+/// # Arguments
+///
+/// * `encounters` - The recorded encounters.
+/// * `now` - The current time of the aircraft state, `seen` is relative to it.
+pub(crate) fn encounters_json(encounters: &VecDeque<Encounter>, now: DateTime<Utc>) -> String {
+    format!(
+        "{{\"now\":{:.1},\"encounters\":[{}]}}",
+        now.timestamp_millis() as f64 / 1000.0,
+        encounters
+            .iter()
+            .map(|encounter| encounter_json(encounter, now))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+fn encounter_json(encounter: &Encounter, now: DateTime<Utc>) -> String {
+    let ra = &encounter.resolution_advisory;
+    let mut fields = vec![
+        format!("\"hex\":\"{:06x}\"", encounter.icao),
+        format!(
+            "\"time\":{:.1}",
+            encounter.timestamp.timestamp_millis() as f64 / 1000.0
+        ),
+        format!("\"seen\":{}", seconds_since(encounter.timestamp, now)),
+    ];
+    if let Some(threat) = encounter.threat {
+        fields.push(format!("\"threat\":\"{:06x}\"", threat));
+    }
+    if let Some(range) = encounter.range {
+        fields.push(format!("\"range\":{:.1}", range));
+    }
+    if let Some(vertical_separation) = encounter.vertical_separation {
+        fields.push(format!("\"vertical_separation\":{}", vertical_separation));
+    }
+    fields.push(format!("\"ara\":{}", ra.ara));
+    fields.push(format!("\"rac\":{}", ra.rac));
+    fields.push(format!("\"active\":{}", ra.is_active()));
+    fields.push(format!("\"advisory\":{}", quoted(&ra.to_string())));
+    format!("{{{}}}", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{ResolutionAdvisory, Threat};
+    use chrono::Duration;
+
+    #[test]
+    fn test_encounters_json() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let encounter = Encounter {
+            timestamp: now,
+            icao: 0x40621D,
+            threat: Some(0x4840D6),
+            resolution_advisory: ResolutionAdvisory {
+                ara: 0b11000010000000,
+                rac: 0,
+                terminated: false,
+                multiple_threats: false,
+                threat: Some(Threat::Icao(0x4840D6)),
+            },
+            range: Some(0.74),
+            vertical_separation: Some(500),
+        };
+        assert_eq!(
+            encounters_json(&VecDeque::from([encounter]), now + Duration::seconds(3)),
+            "{\"now\":1700000003.0,\"encounters\":[{\"hex\":\"40621d\",\"time\":1700000000.0,\"seen\":3.0,\"threat\":\"4840d6\",\"range\":0.7,\"vertical_separation\":500,\"ara\":12416,\"rac\":0,\"active\":true,\"advisory\":\"climb; threat 4840D6\"}]}"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::{aircraft_json, encounters_json};
+use crate::Planes;
+use log::{debug, info, warn};
+use std::{
+    io::{BufRead, BufReader, Result, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+/// Serves `GET /data/aircraft.json` and `GET /data/encounters.json` on the given port from a background thread.
+pub(crate) fn serve_aircraft_json(port: u16, planes: Planes, min_nic: u32) -> Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    info!("Serving aircraft.json on port {}", port);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = respond(stream, &planes, min_nic) {
+                        debug!("HTTP request failed: {}", e);
+                    }
+                }
+                Err(e) => warn!("Failed to accept a client on port {}: {}", port, e),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, planes: &Planes, min_nic: u32) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers, the request has no body
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    debug!("HTTP {}", request_line.trim_end());
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/data/aircraft.json" | "/aircraft.json")) => {
+            let now = *planes.time.read().expect("Time lock poisoned");
+            let aircrafts = planes.aircrafts.read().expect("Planes lock poisoned");
+            ("200 OK", aircraft_json(&aircrafts, now, min_nic))
+        }
+        (Some("GET"), Some("/data/encounters.json" | "/encounters.json")) => {
+            let now = *planes.time.read().expect("Time lock poisoned");
+            let encounters = planes.encounters.read().expect("Encounters lock poisoned");
+            ("200 OK", encounters_json(&encounters, now))
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "{}".to_string()),
+        _ => ("405 Method Not Allowed", "{}".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,230 @@
+use crate::decoder::{AddressType, DF, FlightStatus, Plane};
+use chrono::{DateTime, Utc};
+
+/// Formats a decoded downlink as a BaseStation (SBS-1) `MSG` line.
+///
+/// The transmission type follows the downlink format and ADS-B type code,
+/// the fields are taken from the merged aircraft state after the update.
+///
+/// # Arguments
+///
+/// * `downlink` - The decoded downlink.
+/// * `plane` - The aircraft state updated with the downlink.
+/// * `now` - The reception time of the downlink.
+/// * `min_nic` - Positions with a lower integrity are left out.
+///
+/// # Returns
+///
+/// * `Option<String>` - The CRLF terminated line, `None` for downlinks without a BaseStation equivalent.
+pub(crate) fn sbs_message(
+    downlink: &DF,
+    plane: &Plane,
+    now: DateTime<Utc>,
+    min_nic: u32,
+) -> Option<String> {
+    let mut fields = SbsFields::default();
+    // Positions decoded relative to the observer alone may be aliased, they wait for a global one
+    let fresh_position = plane.position_timestamp == Some(now)
+        && plane.position_confirmed
+        && plane.has_position_integrity(min_nic);
+
+    let transmission = match downlink {
+        DF::EXT(ext) => match ext.message_type.0 {
+            1..=4 => {
+                fields.callsign = plane.ais.clone();
+                1
+            }
+            5..=8 => {
+                fields.altitude = plane.altitude;
+                fields.ground_speed = plane.ground_movement.map(|v| v.round() as u32);
+                fields.track = plane.track;
+                fields.position = fresh_position.then_some((plane.lat, plane.lon));
+                fields.on_ground = Some(true);
+                2
+            }
+            9..=18 => {
+                fields.altitude = plane.altitude;
+                fields.position = fresh_position.then_some((plane.lat, plane.lon));
+                fields.on_ground = Some(false);
+                3
+            }
+            19 => {
+                fields.ground_speed = ext.grspeed;
+                fields.track = ext.track.or(ext.heading);
+                fields.vertical_rate = ext.vrate;
+                4
+            }
+            20..=22 => {
+                fields.altitude = plane.altitude_gnss;
+                fields.position = fresh_position.then_some((plane.lat, plane.lon));
+                fields.on_ground = Some(false);
+                3
+            }
+            _ => return None,
+        },
+        DF::SRT(srt) => match srt.df? {
+            0 | 16 => {
+                fields.altitude = plane.altitude;
+                fields.on_ground = srt.air_air.map(|air_air| air_air.on_ground);
+                7
+            }
+            4 => {
+                fields.altitude = plane.altitude;
+                fields.flight_status(srt.flight_status);
+                5
+            }
+            5 => {
+                fields.squawk = plane.squawk;
+                fields.flight_status(srt.flight_status);
+                6
+            }
+            11 => 8,
+            _ => return None,
+        },
+        DF::MDS(mds) => match mds.df? {
+            20 => {
+                fields.altitude = plane.altitude;
+                fields.flight_status(mds.flight_status);
+                5
+            }
+            21 => {
+                fields.squawk = plane.squawk;
+                fields.flight_status(mds.flight_status);
+                6
+            }
+            _ => return None,
+        },
+    };
+
+    Some(fields.line(transmission, plane.icao, plane.address_type, now))
+}
+
+#[derive(Default)]
+struct SbsFields {
+    callsign: Option<String>,
+    altitude: Option<u32>,
+    ground_speed: Option<u32>,
+    track: Option<u32>,
+    position: Option<(f64, f64)>,
+    vertical_rate: Option<i32>,
+    squawk: Option<u32>,
+    alert: Option<bool>,
+    spi: Option<bool>,
+    on_ground: Option<bool>,
+}
+
+impl SbsFields {
+    /// Takes the alert, SPI and vertical status of a surveillance reply.
+    fn flight_status(&mut self, flight_status: Option<FlightStatus>) {
+        if let Some(flight_status) = flight_status {
+            self.alert = Some(flight_status.alert);
+            self.spi = Some(flight_status.spi);
+            self.on_ground = flight_status.on_ground;
+        }
+    }
+
+    fn line(
+        &self,
+        transmission: u32,
+        icao: u32,
+        address_type: AddressType,
+        now: DateTime<Utc>,
+    ) -> String {
+        let time = now.format("%Y/%m/%d,%H:%M:%S%.3f");
+        let (lat, lon) = match self.position {
+            Some((lat, lon)) => (format!("{:.5}", lat), format!("{:.5}", lon)),
+            None => (String::new(), String::new()),
+        };
+        let emergency = self
+            .squawk
+            .map(|squawk| flag(matches!(squawk, 7500 | 7600 | 7700)));
+        format!(
+            "MSG,{},1,1,{}{:06X},1,{},{},{},{},{},{},{},{},{},{},{},{},{},{}\r\n",
+            transmission,
+            if address_type.is_icao() { "" } else { "~" },
+            icao,
+            time,
+            time,
+            self.callsign.as_deref().unwrap_or_default(),
+            optional(self.altitude),
+            optional(self.ground_speed),
+            optional(self.track),
+            lat,
+            lon,
+            optional(self.vertical_rate),
+            self.squawk.map(|v| format!("{:04}", v)).unwrap_or_default(),
+            self.alert.map(flag).unwrap_or_default(),
+            emergency.unwrap_or_default(),
+            self.spi.map(flag).unwrap_or_default(),
+            self.on_ground.map(flag).unwrap_or_default(),
+        )
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// BaseStation flags are `-1` when set and `0` when clear.
+fn flag(value: bool) -> &'static str {
+    if value { "-1" } else { "0" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::{Downlink, get_message};
+
+    fn plane_and_downlink(squitter: &str, now: DateTime<Utc>) -> (Plane, DF) {
+        let message = get_message(squitter).expect("Failed to get message");
+        let downlink = DF::from_message(&message).expect("Failed to decode downlink");
+        let icao = downlink.icao().expect("Failed to get ICAO");
+        (Plane::from_downlink(&downlink, icao, now), downlink)
+    }
+
+    #[test]
+    fn test_identification() {
+        let now = DateTime::from_timestamp_millis(1_700_000_000_250).expect("Invalid timestamp");
+        let (plane, downlink) = plane_and_downlink("8D4840D6202CC371C32CE0576098", now);
+        assert_eq!(
+            sbs_message(&downlink, &plane, now, 0).as_deref(),
+            Some(
+                "MSG,1,1,1,4840D6,1,2023/11/14,22:13:20.250,2023/11/14,22:13:20.250,KLM1023,,,,,,,,,,,\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_airborne_position() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let (plane, downlink) = plane_and_downlink("8D40621D58C382D690C8AC2863A7", now);
+        let line = sbs_message(&downlink, &plane, now, 0).expect("Failed to format message");
+        let fields = line.trim_end().split(',').collect::<Vec<_>>();
+        assert_eq!(fields.len(), 22);
+        assert_eq!(&fields[..5], ["MSG", "3", "1", "1", "40621D"]);
+        assert_eq!(fields[11], "38000");
+        assert_eq!(fields[21], "0");
+
+        let mut plane = plane;
+        plane.position_timestamp = Some(now);
+        plane.lat = 52.25720;
+        plane.lon = 3.91937;
+        let line = sbs_message(&downlink, &plane, now, 0).expect("Failed to format message");
+        let fields = line.trim_end().split(',').collect::<Vec<_>>();
+        assert_eq!(&fields[14..16], ["", ""]);
+
+        plane.position_confirmed = true;
+        let line = sbs_message(&downlink, &plane, now, 0).expect("Failed to format message");
+        let fields = line.trim_end().split(',').collect::<Vec<_>>();
+        assert_eq!(&fields[14..16], ["52.25720", "3.91937"]);
+    }
+
+    #[test]
+    fn test_surveillance_replies() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).expect("Invalid timestamp");
+        let (plane, downlink) = plane_and_downlink("2800189A8E0F41", now);
+        let line = sbs_message(&downlink, &plane, now, 0).expect("Failed to format message");
+        assert!(line.starts_with("MSG,6,"));
+        assert!(line.ends_with(",5611,0,0,0,0\r\n"));
+    }
+}